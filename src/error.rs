@@ -0,0 +1,20 @@
+use core::fmt;
+
+/// Error returned when an operation would need more slots than a collection's capacity allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The capacity of the collection
+    pub capacity: usize,
+    /// The number of slots the operation needed
+    pub required: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "required {} slots but capacity is {}",
+            self.required, self.capacity
+        )
+    }
+}
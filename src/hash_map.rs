@@ -1,9 +1,13 @@
 use core::{
-    hash::{BuildHasher, Hash, Hasher},
+    hash::{BuildHasher, Hash},
     mem,
 };
 
-use crate::hasher::BuildDefaultHasher;
+#[cfg(feature = "_internal_debug")]
+use crate::hasher::ProbeSlotState;
+use crate::hasher::{
+    BuildDefaultHasher, ConstHashKey, ProbeStrategy, bytes_eq, key_bytes, probe_step, wrap_index,
+};
 
 #[derive(Debug)]
 enum HashMapEntry<K, V>
@@ -91,18 +95,68 @@ where
     build_hasher: H,
     entries: [HashMapEntry<K, V>; N],
     len: usize,
+    // The number of Deleted entries in the backing array
+    tombstones: usize,
+    // Whether to rehash in place once the tombstones pass a threshold
+    auto_compact: bool,
+    probe_strategy: ProbeStrategy,
 }
 
 impl<K, V, const N: usize> HashMap<K, V, N>
 where
     K: Hash + Eq,
 {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
+        assert!(N > 0, "capacity N must be greater than 0");
+
         Self {
             entries: [const { HashMapEntry::Empty }; N],
             len: 0,
             build_hasher: BuildDefaultHasher {},
+            tombstones: 0,
+            auto_compact: false,
+            probe_strategy: ProbeStrategy::Linear,
+        }
+    }
+
+    /// Create a map that automatically compacts itself. See `new_auto_compact_with_hasher`
+    pub fn new_auto_compact() -> Self {
+        Self::new_auto_compact_with_hasher(BuildDefaultHasher {})
+    }
+
+    /// Create a map that resolves collisions with `strategy`
+    pub fn new_with_probe_strategy(strategy: ProbeStrategy) -> Self {
+        Self::new_with_probe_strategy_and_hasher(strategy, BuildDefaultHasher {})
+    }
+}
+
+impl<K, V, const N: usize> Default for HashMap<K, V, N>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize> FromIterator<(K, V)> for HashMap<K, V, N>
+where
+    K: Hash + Eq,
+{
+    /// Build a map from key-value pairs. If a key appears more than once the last value wins.
+    /// Panics if there are more than `N` distinct keys
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+
+        for (key, val) in iter {
+            if let Some(existing) = map.get_mut(&key) {
+                *existing = val;
+            } else if !map.insert(key, val) {
+                panic!("Attempt to add element to full HashMap");
+            }
         }
+
+        map
     }
 }
 
@@ -112,32 +166,153 @@ where
     H: BuildHasher,
 {
     pub fn new_with_hasher(hasher: H) -> Self {
+        assert!(N > 0, "capacity N must be greater than 0");
+
         Self {
             entries: [const { HashMapEntry::Empty }; N],
             len: 0,
             build_hasher: hasher,
+            tombstones: 0,
+            auto_compact: false,
+            probe_strategy: ProbeStrategy::Linear,
         }
     }
 
-    pub fn insert(&mut self, key: K, val: V) -> bool {
-        if let Some(spot) = self.probe_for_available_spot(&key) {
-            self.entries[spot] = HashMapEntry::Occupied(key, val);
-            self.len += 1;
-            true
-        } else {
-            false
+    /// Create a map that automatically compacts itself. Removing entries leaves tombstones in the
+    /// backing array, which make probing slower. When more than `N / 4` tombstones have built up,
+    /// the next insert first rehashes the table in place to clear them. A rehash costs O(N), but
+    /// it can happen at most once every `N / 4` removals, so the amortized cost per removal is
+    /// O(1)
+    pub fn new_auto_compact_with_hasher(hasher: H) -> Self {
+        Self {
+            auto_compact: true,
+            ..Self::new_with_hasher(hasher)
         }
     }
 
+    /// Create a map that resolves collisions with `strategy`. The strategy can't be changed once
+    /// the map is created, since entries are placed along the probe path it defines
+    pub fn new_with_probe_strategy_and_hasher(strategy: ProbeStrategy, hasher: H) -> Self {
+        Self {
+            probe_strategy: strategy,
+            ..Self::new_with_hasher(hasher)
+        }
+    }
+
+    /// The strategy used to resolve collisions
+    pub fn probe_strategy(&self) -> ProbeStrategy {
+        self.probe_strategy
+    }
+
+    pub fn insert(&mut self, key: K, val: V) -> bool {
+        self.insert_at_spot(key, val).is_some()
+    }
+
+    /// Check whether `insert` would succeed for `key`, without modifying the map. This is false if
+    /// the key is already present or there is no free spot for it
+    pub fn can_insert(&self, key: &K) -> bool {
+        self.probe_for_available_spot(key).is_some()
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn remove(&mut self, key: &K) -> Option<V> {
-        let spot = self.probe_for_existing_spot(key)?;
+        self.remove_at_spot(key).map(|(_, val)| val)
+    }
 
-        self.len -= 1;
-        self.entries[spot].take().into()
+    /// Remove all entries for which `f` returns false
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.retain_count(f);
+    }
+
+    /// Remove all entries for which `f` returns false, returning the number of entries removed
+    pub fn retain_count<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let mut removed = 0;
+
+        for entry in self.entries.iter_mut() {
+            if let HashMapEntry::Occupied(key, val) = entry
+                && !f(key, val)
+            {
+                entry.take();
+                removed += 1;
+            }
+        }
+
+        self.len -= removed;
+        self.tombstones += removed;
+        removed
+    }
+
+    /// The number of deleted markers (tombstones) left in the backing array by removals
+    pub fn tombstone_count(&self) -> usize {
+        self.tombstones
+    }
+
+    /// Clear all tombstones by rehashing the table in place, without changing its capacity. Every
+    /// entry is moved to the earliest free spot on its probe path. This costs O(N) and needs no
+    /// scratch space beyond an array of `N` flags on the stack
+    pub fn compact_in_place(&mut self) {
+        if self.tombstones > 0 {
+            self.rehash_in_place();
+        }
+    }
+
+    /// Remove all tombstones, keeping every entry. This is the same as `compact_in_place`
+    pub fn clear_tombstones(&mut self) {
+        self.compact_in_place();
+    }
+
+    /// Move all entries into a map with a capacity of `M`, returning `Err(self)` if they don't fit.
+    /// The new map contains no tombstones
+    pub fn repack_into<const M: usize>(self) -> Result<HashMap<K, V, M, H>, Self> {
+        if self.len > M {
+            return Err(self);
+        }
+
+        let Self {
+            build_hasher,
+            entries,
+            probe_strategy,
+            auto_compact,
+            ..
+        } = self;
+
+        let mut map: HashMap<K, V, M, H> = HashMap {
+            auto_compact,
+            ..HashMap::new_with_probe_strategy_and_hasher(probe_strategy, build_hasher)
+        };
+        for entry in entries {
+            if let HashMapEntry::Occupied(key, val) = entry {
+                map.insert(key, val);
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Consume the map, iterating over its keys in backing-array order
+    pub fn into_keys(self) -> impl Iterator<Item = K> {
+        self.entries.into_iter().filter_map(|entry| match entry {
+            HashMapEntry::Occupied(key, _) => Some(key),
+            _ => None,
+        })
+    }
+
+    /// Consume the map, iterating over its values in backing-array order
+    pub fn into_values(self) -> impl Iterator<Item = V> {
+        self.entries.into_iter().filter_map(Option::<V>::from)
     }
 
     pub fn contains_key(&self, key: &K) -> bool {
@@ -156,11 +331,218 @@ where
         self.entries[spot].as_mut_val()
     }
 
+    /// Get a mutable reference to the value for `key`, first inserting `V::default()` if the key is
+    /// not present. Panics if the key is not present and the map is full
+    pub fn get_or_insert_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        let spot = match self.probe_for_existing_spot(&key) {
+            Some(spot) => spot,
+            None => self
+                .insert_at_spot(key, V::default())
+                .unwrap_or_else(|| panic!("Attempt to add element to full HashMap")),
+        };
+
+        self.entries[spot]
+            .as_mut_val()
+            .unwrap_or_else(|| panic!("Unexpected non-occupied spot {} for key", spot))
+    }
+
+    /// Get a mutable reference to the value for `key`, first inserting the value returned by `f` if
+    /// the key is not present. If `f` fails, its error is returned and the map is left unchanged.
+    /// Panics if the key is not present and the map is full
+    pub fn get_or_try_insert_with<E, F>(&mut self, key: K, f: F) -> Result<&mut V, E>
+    where
+        F: FnOnce() -> Result<V, E>,
+    {
+        let spot = match self.probe_for_existing_spot(&key) {
+            Some(spot) => spot,
+            None => {
+                let val = f()?;
+                self.insert_at_spot(key, val)
+                    .unwrap_or_else(|| panic!("Attempt to add element to full HashMap"))
+            }
+        };
+
+        Ok(self.entries[spot]
+            .as_mut_val()
+            .unwrap_or_else(|| panic!("Unexpected non-occupied spot {} for key", spot)))
+    }
+
+    /// Iterate over the keys of the map in ascending order. This packs references to the keys into
+    /// a scratch array of size `N` and sorts them on every call, so it costs O(N + n log n) rather
+    /// than O(n)
+    pub fn keys_sorted(&self) -> impl Iterator<Item = &K>
+    where
+        K: Ord,
+    {
+        let mut keys = [None; N];
+        let occupied = self.entries.iter().filter_map(|entry| match entry {
+            HashMapEntry::Occupied(k, _) => Some(k),
+            _ => None,
+        });
+        for (slot, k) in keys.iter_mut().zip(occupied) {
+            *slot = Some(k);
+        }
+
+        // Only the first `len` spots are filled, so only they need sorting
+        keys[..self.len].sort_unstable();
+        keys.into_iter().take(self.len).flatten()
+    }
+
+    /// Get mutable references to the values of several distinct keys at once. Returns `None` if
+    /// any key is missing or if any two keys are equal
+    pub fn get_many_mut<const M: usize>(&mut self, keys: [&K; M]) -> Option<[&mut V; M]> {
+        let mut spots = [0; M];
+        for (i, key) in keys.iter().enumerate() {
+            spots[i] = self.probe_for_existing_spot(key)?;
+
+            if spots[..i].contains(&spots[i]) {
+                return None;
+            }
+        }
+
+        let mut vals: [Option<&mut V>; M] = [const { None }; M];
+        for (spot, entry) in self.entries.iter_mut().enumerate() {
+            if let Some(i) = spots.iter().position(|s| *s == spot) {
+                vals[i] = entry.as_mut_val();
+            }
+        }
+
+        Some(vals.map(|v| v.unwrap_or_else(|| panic!("Unexpected non-occupied spot for key"))))
+    }
+
+    /// Insert a key-value pair, returning the spot in the backing array it was placed in
+    pub(crate) fn insert_at_spot(&mut self, key: K, val: V) -> Option<usize> {
+        if self.auto_compact && self.tombstones > N / 4 {
+            self.rehash_in_place();
+        }
+
+        let spot = self.probe_for_available_spot(&key)?;
+
+        if let HashMapEntry::Deleted = self.entries[spot] {
+            self.tombstones -= 1;
+        }
+        self.entries[spot] = HashMapEntry::Occupied(key, val);
+        self.inc_len();
+        Some(spot)
+    }
+
+    /// Remove a key, returning the spot in the backing array it was removed from along with its
+    /// value
+    pub(crate) fn remove_at_spot(&mut self, key: &K) -> Option<(usize, V)> {
+        let spot = self.probe_for_existing_spot(key)?;
+
+        self.dec_len();
+        self.tombstones += 1;
+        let val: Option<V> = self.entries[spot].take().into();
+        val.map(|val| (spot, val))
+    }
+
+    /// Get the key-value pair stored at a specific spot in the backing array, if it is occupied
+    pub(crate) fn entry_at_spot(&self, spot: usize) -> Option<(&K, &V)> {
+        self.entries[spot].as_ref().into()
+    }
+
+    /// Rebuild the table in place, clearing all tombstones and moving every entry to the earliest
+    /// free spot on its probe path
+    fn rehash_in_place(&mut self) {
+        // pending[i] is true while the entry at i still needs to be moved to its final spot
+        let mut pending = [false; N];
+
+        for (i, entry) in self.entries.iter_mut().enumerate() {
+            match entry {
+                HashMapEntry::Occupied(..) => pending[i] = true,
+                HashMapEntry::Deleted => *entry = HashMapEntry::Empty,
+                HashMapEntry::Empty => {}
+            }
+        }
+
+        for i in 0..N {
+            while pending[i] {
+                let (mut spot, step) = match &self.entries[i] {
+                    HashMapEntry::Occupied(key, _) => self.probe_start(key),
+                    _ => panic!("Unexpected non-occupied pending spot {} during rehash", i),
+                };
+
+                // Every spot before the first empty or pending one on the probe path is occupied
+                // by an entry that has been placed. Spot i is pending, so this always terminates
+                while !pending[spot] && !matches!(self.entries[spot], HashMapEntry::Empty) {
+                    spot = wrap_index::<N>(spot + step);
+                }
+
+                if spot == i {
+                    pending[i] = false;
+                } else {
+                    // Place the entry, and if it displaced a pending entry, process that one next
+                    self.entries.swap(i, spot);
+                    pending[i] = pending[spot];
+                    pending[spot] = false;
+                }
+            }
+        }
+
+        self.tombstones = 0;
+    }
+
+    /// Increment the length, asserting in debug builds that it stays within the capacity
+    fn inc_len(&mut self) {
+        debug_assert!(
+            self.len < N,
+            "Attempt to increment len {} past capacity {}",
+            self.len,
+            N
+        );
+        self.len += 1;
+    }
+
+    /// Decrement the length, asserting in debug builds that it doesn't go below zero
+    fn dec_len(&mut self) {
+        debug_assert!(self.len > 0, "Attempt to decrement len below 0");
+        self.len -= 1;
+    }
+
+    /// Iterate over the slots visited when looking up `key`, in order, along with the state of
+    /// each. The sequence ends at the first Empty slot, at the slot holding `key`, or after every
+    /// slot has been visited
+    #[cfg(feature = "_internal_debug")]
+    pub fn probe_sequence(&self, key: &K) -> impl Iterator<Item = (usize, ProbeSlotState)> {
+        let (start, step) = self.probe_start(key);
+        let mut next = Some(start);
+
+        core::iter::from_fn(move || {
+            let spot = next?;
+            let mut done = false;
+            let state = match &self.entries[spot] {
+                HashMapEntry::Empty => {
+                    done = true;
+                    ProbeSlotState::Empty
+                }
+                HashMapEntry::Deleted => ProbeSlotState::Deleted,
+                HashMapEntry::Occupied(found, _) => {
+                    done = found == key;
+                    ProbeSlotState::Occupied
+                }
+            };
+
+            let following = wrap_index::<N>(spot + step);
+            next = (!done && following != start).then_some(following);
+            Some((spot, state))
+        })
+    }
+
     fn hash_key(&self, key: &K) -> u64 {
-        let mut hasher = self.build_hasher.build_hasher();
-        key.hash(&mut hasher);
+        self.build_hasher.hash_one(key)
+    }
 
-        hasher.finish()
+    /// Get the first spot on the probe path for `key`, and the distance between spots on it
+    fn probe_start(&self, key: &K) -> (usize, usize) {
+        let hash = self.hash_key(key);
+        (
+            wrap_index::<N>(hash as usize),
+            probe_step::<N>(self.probe_strategy, hash),
+        )
     }
 
     fn probe_for_available_spot(&self, key: &K) -> Option<usize> {
@@ -168,25 +550,33 @@ where
             return None;
         }
 
-        let hash = self.hash_key(key);
-        let mut spot = hash as usize % N;
+        let (mut spot, step) = self.probe_start(key);
         let original_spot = spot;
 
+        let mut first_deleted = None;
+
         loop {
             match &self.entries[spot] {
-                HashMapEntry::Empty | HashMapEntry::Deleted => {
-                    return Some(spot);
+                HashMapEntry::Empty => {
+                    return Some(first_deleted.unwrap_or(spot));
+                }
+                HashMapEntry::Deleted => {
+                    // The key may still be further along the probe path, so keep looking
+                    first_deleted.get_or_insert(spot);
+                    spot = wrap_index::<N>(spot + step);
                 }
                 HashMapEntry::Occupied(k, _) => {
                     if k == key {
                         return None;
                     }
-                    spot = (spot + 1) % N;
+                    spot = wrap_index::<N>(spot + step);
                 }
             }
 
             if spot == original_spot {
-                panic!("Unable to find free spot in HashMap with len < N")
+                return Some(first_deleted.unwrap_or_else(|| {
+                    panic!("Unable to find free spot in HashMap with len < N")
+                }));
             }
         }
     }
@@ -196,8 +586,7 @@ where
             return None;
         }
 
-        let hash = self.hash_key(key);
-        let mut spot = hash as usize % N;
+        let (mut spot, step) = self.probe_start(key);
         let original_spot = spot;
 
         loop {
@@ -206,13 +595,13 @@ where
                     return None;
                 }
                 HashMapEntry::Deleted => {
-                    spot = (spot + 1) % N;
+                    spot = wrap_index::<N>(spot + step);
                 }
                 HashMapEntry::Occupied(k, _) => {
                     if k == key {
                         return Some(spot);
                     } else {
-                        spot = (spot + 1) % N
+                        spot = wrap_index::<N>(spot + step)
                     }
                 }
             }
@@ -224,6 +613,104 @@ where
     }
 }
 
+/// Two maps are equal if they contain the same keys, each with an equal value. Where the entries
+/// sit in the backing arrays doesn't matter
+impl<K, V, const N: usize, H> PartialEq for HashMap<K, V, N, H>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    H: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self.entries.iter().all(|entry| match entry {
+                HashMapEntry::Occupied(key, val) => other.get(key) == Some(val),
+                _ => true,
+            })
+    }
+}
+
+impl<K, V, const N: usize, H> Eq for HashMap<K, V, N, H>
+where
+    K: Hash + Eq,
+    V: Eq,
+    H: BuildHasher,
+{
+}
+
+impl<K, const N: usize, H> HashMap<K, u32, N, H>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    /// Increment the count for `key`, starting it at 1 if the key is not present. Returns the new
+    /// count. Panics if the key is not present and the map is full
+    pub fn count(&mut self, key: K) -> u32 {
+        let count = self.get_or_insert_default(key);
+        *count += 1;
+        *count
+    }
+}
+
+impl<K, V, const N: usize, const SEED: u64> HashMap<K, V, N, BuildDefaultHasher<SEED>>
+where
+    K: Hash + Eq + ConstHashKey,
+{
+    /// Insert a key-value pair like `insert`, but usable in const contexts. If the key is already
+    /// present or the map is full, the map is left unchanged and the value is returned in `Err`
+    pub const fn insert_const(&mut self, key: K, val: V) -> Result<(), V> {
+        if self.len >= N {
+            return Err(val);
+        }
+
+        let bytes = key_bytes(&key);
+        let hash = BuildDefaultHasher::<SEED>::hash_bytes_const(bytes);
+        let mut spot = wrap_index::<N>(hash as usize);
+        let step = probe_step::<N>(self.probe_strategy, hash);
+        let original_spot = spot;
+        let mut first_deleted = None;
+
+        let spot = loop {
+            match &self.entries[spot] {
+                HashMapEntry::Empty => {
+                    break match first_deleted {
+                        Some(deleted) => deleted,
+                        None => spot,
+                    };
+                }
+                HashMapEntry::Deleted => {
+                    if first_deleted.is_none() {
+                        first_deleted = Some(spot);
+                    }
+                }
+                HashMapEntry::Occupied(k, _) => {
+                    if bytes_eq(key_bytes(k), bytes) {
+                        return Err(val);
+                    }
+                }
+            }
+
+            spot = wrap_index::<N>(spot + step);
+            if spot == original_spot {
+                match first_deleted {
+                    Some(deleted) => break deleted,
+                    None => panic!("Unable to find free spot in HashMap with len < N"),
+                }
+            }
+        };
+
+        let old = mem::replace(&mut self.entries[spot], HashMapEntry::Occupied(key, val));
+        if let HashMapEntry::Deleted = old {
+            self.tombstones -= 1;
+        }
+        // The old entry is Empty or Deleted, so there is nothing to drop. Forgetting it avoids
+        // running a destructor, which isn't allowed in const contexts
+        mem::forget(old);
+        self.len += 1;
+        Ok(())
+    }
+}
+
 #[macro_export]
 macro_rules! map {
     [$(($key:expr, $value:expr)),*] => {{
@@ -234,8 +721,26 @@ macro_rules! map {
     }};
 }
 
+/// Build a `HashMap` in a const context, such as the initializer of a `static` or `const`. Keys
+/// must implement `ConstHashKey` and the map uses `BuildDefaultHasher`. Fails to compile if a key
+/// is repeated or there are more pairs than the capacity
+#[macro_export]
+macro_rules! const_map {
+    [$(($key:expr, $value:expr)),* $(,)?] => {{
+        #[allow(unused_mut)]
+        let mut map = $crate::HashMap::new();
+        $(
+            if map.insert_const($key, $value).is_err() {
+                panic!("Attempt to add duplicate key or add element to full HashMap");
+            }
+        )*
+        map
+    }};
+}
+
 #[cfg(test)]
 mod tests {
+    use core::hash::Hasher;
 
     use super::*;
 
@@ -416,4 +921,651 @@ mod tests {
         assert_eq!(map.get(&4), Some(&4.0));
         assert_eq!(map.get(&5), Some(&5.0));
     }
+
+    #[test]
+    fn test_get_many_mut() {
+        let mut map: HashMap<_, _, 50> = map!((1, 1.0), (2, 2.0), (3, 3.0));
+
+        let [a, b] = map.get_many_mut([&1, &3]).unwrap();
+        *a += 10.0;
+        *b += 30.0;
+
+        assert_eq!(map.get(&1), Some(&11.0));
+        assert_eq!(map.get(&2), Some(&2.0));
+        assert_eq!(map.get(&3), Some(&33.0));
+
+        assert!(map.get_many_mut([&1, &1]).is_none());
+        assert!(map.get_many_mut([&1, &4]).is_none());
+    }
+
+    #[test]
+    fn test_keys_sorted() {
+        let map: HashMap<u32, (), 50> = map!((300, ()), (7, ()), (42, ()), (1, ()), (250, ()));
+
+        let mut keys = map.keys_sorted();
+        assert_eq!(keys.next(), Some(&1));
+        assert_eq!(keys.next(), Some(&7));
+        assert_eq!(keys.next(), Some(&42));
+        assert_eq!(keys.next(), Some(&250));
+        assert_eq!(keys.next(), Some(&300));
+        assert_eq!(keys.next(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to increment len 2 past capacity 2")]
+    fn test_inc_len_past_capacity() {
+        let mut map = HashMap::<u32, u32, 2>::new();
+        map.insert(1, 10);
+        map.insert(2, 20);
+
+        map.inc_len();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to decrement len below 0")]
+    fn test_dec_len_below_zero() {
+        let mut map = HashMap::<u32, u32, 2>::new();
+        map.dec_len();
+    }
+
+    #[test]
+    fn test_get_or_insert_default() {
+        let mut map: HashMap<char, u32, 10> = HashMap::new();
+
+        for c in ['a', 'b', 'a', 'c', 'a', 'b'] {
+            *map.get_or_insert_default(c) += 1;
+        }
+
+        assert_eq!(map.len, 3);
+        assert_eq!(map.get(&'a'), Some(&3));
+        assert_eq!(map.get(&'b'), Some(&2));
+        assert_eq!(map.get(&'c'), Some(&1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full HashMap")]
+    fn test_get_or_insert_default_full_panic() {
+        let mut map: HashMap<u32, u32, 2> = map!((1, 1), (2, 2));
+        *map.get_or_insert_default(1) += 1;
+        *map.get_or_insert_default(3) += 1;
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let map: HashMap<u32, char, 10> = [(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+
+        assert_eq!(map.len, 3);
+        assert_eq!(map.get(&1), Some(&'a'));
+        assert_eq!(map.get(&2), Some(&'b'));
+        assert_eq!(map.get(&3), Some(&'c'));
+
+        let map: HashMap<u32, char, 10> = [(1, 'a'), (2, 'b'), (1, 'c')].into_iter().collect();
+
+        assert_eq!(map.len, 2);
+        assert_eq!(map.get(&1), Some(&'c'));
+        assert_eq!(map.get(&2), Some(&'b'));
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full HashMap")]
+    fn test_from_iter_full_panic() {
+        let _: HashMap<u32, u32, 2> = [(1, 1), (2, 2), (3, 3)].into_iter().collect();
+    }
+
+    #[test]
+    fn test_repack_into() {
+        let mut map: HashMap<u32, u32, 64> =
+            map!((1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60));
+        map.remove(&2);
+        map.remove(&4);
+        map.remove(&6);
+
+        assert_eq!(map.tombstone_count(), 3);
+
+        let map: HashMap<u32, u32, 8> = map
+            .repack_into()
+            .unwrap_or_else(|_| panic!("Expected map to fit"));
+
+        assert_eq!(map.tombstone_count(), 0);
+        assert_eq!(map.len, 3);
+        assert_eq!(map.get(&1), Some(&10));
+        assert_eq!(map.get(&3), Some(&30));
+        assert_eq!(map.get(&5), Some(&50));
+        assert_eq!(map.get(&2), None);
+
+        let map = map.repack_into::<2>().err().unwrap();
+        assert_eq!(map.len, 3);
+    }
+
+    #[test]
+    fn test_retain_count() {
+        let mut map: HashMap<u32, u32, 50> = map!((1, 10), (2, 20), (3, 30), (4, 40));
+
+        assert_eq!(
+            map.retain_count(|k, v| {
+                *v += 1;
+                k % 2 == 0
+            }),
+            2
+        );
+        assert_eq!(map.len, 2);
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.get(&2), Some(&21));
+        assert_eq!(map.get(&3), None);
+        assert_eq!(map.get(&4), Some(&41));
+
+        map.retain(|k, _| *k != 2);
+        assert_eq!(map.len, 1);
+        assert_eq!(map.get(&2), None);
+    }
+
+    #[test]
+    fn test_rehash_in_place() {
+        let bh = IntCollBuildHasher {};
+        let mut map: HashMap<_, _, 8, _> = HashMap::new_with_hasher(bh);
+
+        for i in 1..=6 {
+            map.insert(i, i as f64);
+        }
+        map.remove(&1);
+        map.remove(&2);
+        map.remove(&4);
+
+        assert_eq!(map.tombstone_count(), 3);
+
+        map.rehash_in_place();
+
+        assert_eq!(map.tombstone_count(), 0);
+        assert_eq!(map.entries[0], HashMapEntry::Occupied(3, 3.0));
+        assert_eq!(map.entries[1], HashMapEntry::Occupied(5, 5.0));
+        assert_eq!(map.entries[2], HashMapEntry::Occupied(6, 6.0));
+        for i in 3..8 {
+            assert_eq!(map.entries[i], HashMapEntry::Empty);
+        }
+
+        assert_eq!(map.len, 3);
+        assert_eq!(map.get(&3), Some(&3.0));
+        assert_eq!(map.get(&5), Some(&5.0));
+        assert_eq!(map.get(&6), Some(&6.0));
+    }
+
+    #[test]
+    fn test_auto_compact() {
+        let mut map: HashMap<u32, u32, 16> = HashMap::new_auto_compact();
+        let mut manual: HashMap<u32, u32, 16> = HashMap::new();
+
+        for i in 0..200 {
+            map.insert(i, i);
+            manual.insert(i, i);
+            if i >= 8 {
+                assert_eq!(map.remove(&(i - 8)), Some(i - 8));
+                manual.remove(&(i - 8));
+            }
+
+            assert!(map.tombstone_count() <= 16 / 4 + 1);
+        }
+
+        assert_eq!(map.len, 8);
+        for i in 192..200 {
+            assert_eq!(map.get(&i), Some(&i));
+        }
+
+        assert!(manual.tombstone_count() > 16 / 4 + 1);
+    }
+
+    #[test]
+    fn test_insert_existing_key_after_tombstone() {
+        let bh = IntCollBuildHasher {};
+        let mut map: HashMap<_, _, 50, _> = HashMap::new_with_hasher(bh);
+
+        map.insert(1, 1.0);
+        map.insert(2, 2.0);
+        map.remove(&1);
+
+        assert!(!map.insert(2, 3.0));
+        assert_eq!(map.len, 1);
+        assert_eq!(map.remove(&2), Some(2.0));
+        assert!(!map.contains_key(&2));
+    }
+
+    #[test]
+    fn test_into_keys_values() {
+        let map: HashMap<u32, u32, 50> = map!((300, 1), (7, 2), (42, 3));
+        let len = map.len();
+
+        let mut keys = [0; 3];
+        for (i, key) in map.into_keys().enumerate() {
+            keys[i] = key;
+        }
+        keys.sort();
+        assert_eq!(keys, [7, 42, 300]);
+        assert_eq!(keys.len(), len);
+
+        let map: HashMap<u32, u32, 50> = map!((300, 1), (7, 2), (42, 3));
+        assert_eq!(map.into_values().sum::<u32>(), 6);
+
+        let map: HashMap<u32, u32, 50> = map!((300, 1), (7, 2), (42, 3));
+        assert_eq!(map.into_keys().count(), 3);
+    }
+
+    #[test]
+    fn test_count() {
+        let mut map: HashMap<char, u32, 10> = HashMap::new();
+
+        for c in ['a', 'b', 'a', 'a', 'c'] {
+            map.count(c);
+        }
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&'a'), Some(&3));
+        assert_eq!(map.get(&'b'), Some(&1));
+        assert_eq!(map.get(&'c'), Some(&1));
+        assert_eq!(map.count('b'), 2);
+    }
+
+    #[test]
+    fn test_power_of_two_capacity() {
+        let mut pow2: HashMap<u32, u32, 64> = HashMap::new();
+        let mut generic: HashMap<u32, u32, 63> = HashMap::new();
+
+        for i in 0..60 {
+            let key = i * 37 + 5;
+            pow2.insert(key, i);
+            generic.insert(key, i);
+        }
+        for i in (0..60).step_by(3) {
+            let key = i * 37 + 5;
+            assert_eq!(pow2.remove(&key), generic.remove(&key));
+        }
+
+        for i in 0..60 {
+            let key = i * 37 + 5;
+            assert_eq!(pow2.get(&key), generic.get(&key));
+        }
+        assert_eq!(pow2.len(), 40);
+        assert_eq!(generic.len(), 40);
+    }
+
+    #[test]
+    #[cfg(feature = "_internal_debug")]
+    fn test_probe_sequence() {
+        use crate::hasher::ProbeSlotState::*;
+
+        let bh = IntCollBuildHasher {};
+        let mut map: HashMap<_, _, 5, _> = HashMap::new_with_hasher(bh);
+        map.insert(1, ());
+        map.insert(2, ());
+        map.insert(3, ());
+        map.remove(&2);
+
+        assert!(map.probe_sequence(&4).eq([
+            (0, Occupied),
+            (1, Deleted),
+            (2, Occupied),
+            (3, Empty)
+        ]));
+        assert!(
+            map.probe_sequence(&3)
+                .eq([(0, Occupied), (1, Deleted), (2, Occupied)])
+        );
+        assert!(map.probe_sequence(&1).eq([(0, Occupied)]));
+    }
+
+    #[test]
+    fn test_eq() {
+        let map1: HashMap<u32, u32, 10> = map!((1, 10), (2, 20), (3, 30));
+        let map2: HashMap<u32, u32, 10> = map!((3, 30), (1, 10), (2, 20));
+        assert!(map1 == map2);
+
+        let map3: HashMap<u32, u32, 10> = map!((1, 10), (2, 20), (3, 31));
+        assert!(map1 != map3);
+
+        let map4: HashMap<u32, u32, 10> = map!((1, 10), (2, 20));
+        assert!(map1 != map4);
+        assert!(map4 != map1);
+    }
+
+    #[test]
+    fn test_eq_tombstones() {
+        let bh = IntCollBuildHasher {};
+        let mut map1: HashMap<_, _, 10, _> = HashMap::new_with_hasher(bh);
+        map1.insert(4, 40);
+        map1.insert(1, 10);
+        map1.insert(5, 50);
+        map1.insert(2, 20);
+        map1.remove(&4);
+        map1.remove(&5);
+
+        let bh = IntCollBuildHasher {};
+        let mut map2: HashMap<_, _, 10, _> = HashMap::new_with_hasher(bh);
+        map2.insert(2, 20);
+        map2.insert(1, 10);
+
+        assert_eq!(map1.tombstone_count(), 2);
+        assert!(map1 == map2);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity N must be greater than 0")]
+    fn test_zero_capacity_panic() {
+        let mut map = HashMap::<u32, u32, 0>::new();
+        map.insert(1, 1);
+    }
+
+    #[test]
+    fn test_can_insert() {
+        let bh = IntCollBuildHasher {};
+        let mut map: HashMap<_, _, 4, _> = HashMap::new_with_hasher(bh);
+        map.insert(1, ());
+        map.insert(2, ());
+        map.insert(3, ());
+        map.remove(&1);
+        map.insert(4, ());
+
+        for key in 0..7 {
+            let expected = map.can_insert(&key);
+            assert_eq!(map.insert(key, ()), expected);
+            if expected {
+                map.remove(&key);
+            }
+        }
+
+        assert!(map.can_insert(&5));
+        assert!(!map.can_insert(&2));
+        map.insert(5, ());
+        assert!(!map.can_insert(&6));
+        assert!(!map.insert(6, ()));
+    }
+
+    #[test]
+    fn test_get_or_try_insert_with() {
+        let mut map: HashMap<u32, u32, 10> = HashMap::new();
+
+        assert_eq!(
+            map.get_or_try_insert_with(1, || Err("parse error")),
+            Err("parse error")
+        );
+        assert!(!map.contains_key(&1));
+        assert_eq!(map.len(), 0);
+
+        let val = map.get_or_try_insert_with(1, || Ok::<_, &str>(10)).unwrap();
+        assert_eq!(*val, 10);
+        *val += 1;
+        assert_eq!(map.get(&1), Some(&11));
+
+        // The closure isn't called when the key is already present
+        assert_eq!(map.get_or_try_insert_with(1, || Err("unused")), Ok(&mut 11));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_compact_in_place() {
+        let mut map: HashMap<u32, u32, 16> = HashMap::new();
+        for i in 0..12 {
+            map.insert(i * 5, i);
+        }
+        for i in (0..12).step_by(2) {
+            map.remove(&(i * 5));
+        }
+        assert_eq!(map.tombstone_count(), 6);
+
+        map.compact_in_place();
+
+        assert_eq!(map.tombstone_count(), 0);
+        assert!(!map.entries.contains(&HashMapEntry::Deleted));
+        assert_eq!(map.len(), 6);
+        for i in 0..12 {
+            let expected = if i % 2 == 1 { Some(&i) } else { None };
+            assert_eq!(map.get(&(i * 5)), expected);
+        }
+    }
+
+    #[test]
+    fn test_pow2_and_non_pow2_capacities() {
+        let mut pow2: HashMap<u32, u32, 16> = HashMap::new();
+        let mut non_pow2: HashMap<u32, u32, 50> = HashMap::new();
+
+        for i in 0..16 {
+            pow2.insert(i * 1000 + 3, i);
+        }
+        for i in 0..50 {
+            non_pow2.insert(i * 1000 + 3, i);
+        }
+
+        for i in 0..16 {
+            assert_eq!(pow2.get(&(i * 1000 + 3)), Some(&i));
+        }
+        for i in 0..50 {
+            assert_eq!(non_pow2.get(&(i * 1000 + 3)), Some(&i));
+        }
+        assert!(!pow2.contains_key(&4));
+        assert!(!non_pow2.contains_key(&4));
+    }
+
+    #[test]
+    fn test_clear_tombstones() {
+        let build = || {
+            let bh = IntCollBuildHasher {};
+            let mut map: HashMap<_, _, 10, _> = HashMap::new_with_hasher(bh);
+            for i in 0..8 {
+                map.insert(i, i * 10);
+            }
+            map.remove(&1);
+            map.remove(&4);
+            map.remove(&6);
+            map
+        };
+
+        let mut map = build();
+        assert_eq!(map.tombstone_count(), 3);
+
+        map.clear_tombstones();
+        assert_eq!(map.tombstone_count(), 0);
+        assert_eq!(map.len(), 5);
+        for i in [0, 2, 3, 5, 7] {
+            assert_eq!(map.get(&i), Some(&(i * 10)));
+        }
+
+        // The same operations always produce the same layout
+        let mut other = build();
+        other.clear_tombstones();
+        assert_eq!(map.entries, other.entries);
+    }
+
+    #[test]
+    fn test_const_map() {
+        static MAP: HashMap<u32, u32, 16> = const_map![(1, 10), (17, 170), (2, 20), (300, 3)];
+        const EMPTY: HashMap<u32, u32, 4> = const_map![];
+
+        assert_eq!(MAP.len(), 4);
+        assert_eq!(MAP.get(&1), Some(&10));
+        assert_eq!(MAP.get(&17), Some(&170));
+        assert_eq!(MAP.get(&2), Some(&20));
+        assert_eq!(MAP.get(&300), Some(&3));
+        assert_eq!(MAP.get(&3), None);
+        assert!(EMPTY.is_empty());
+    }
+
+    #[test]
+    fn test_insert_const_matches_insert() {
+        let mut const_inserted = HashMap::<i64, u32, 8>::new();
+        let mut inserted = HashMap::<i64, u32, 8>::new();
+
+        for key in [-1, 7, 15, 23, 1 << 40] {
+            assert_eq!(const_inserted.insert_const(key, 1), Ok(()));
+            assert!(inserted.insert(key, 1));
+        }
+        assert_eq!(const_inserted.insert_const(7, 2), Err(2));
+        assert!(const_inserted.entries == inserted.entries);
+
+        // Tombstones left by a runtime removal are reused like in insert
+        const_inserted.remove(&15);
+        inserted.remove(&15);
+        assert_eq!(const_inserted.insert_const(31, 1), Ok(()));
+        assert!(inserted.insert(31, 1));
+        assert!(const_inserted.entries == inserted.entries);
+        assert_eq!(const_inserted.tombstone_count(), inserted.tombstone_count());
+    }
+
+    #[test]
+    fn test_insert_const_full() {
+        let mut map = HashMap::<u8, u8, 2>::new();
+        assert_eq!(map.insert_const(1, 1), Ok(()));
+        assert_eq!(map.insert_const(2, 2), Ok(()));
+        assert_eq!(map.insert_const(3, 3), Err(3));
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add duplicate key or add element to full HashMap")]
+    fn test_const_map_duplicate_panic() {
+        let _: HashMap<u32, u32, 4> = const_map![(1, 1), (1, 2)];
+    }
+
+    // The number of slots visited to find `key`, including the one holding it
+    fn probe_len<K, V, const N: usize, H>(map: &HashMap<K, V, N, H>, key: &K) -> usize
+    where
+        K: Hash + Eq,
+        H: BuildHasher,
+    {
+        let (mut spot, step) = map.probe_start(key);
+        let mut len = 1;
+
+        while !matches!(&map.entries[spot], HashMapEntry::Occupied(k, _) if k == key) {
+            spot = wrap_index::<N>(spot + step);
+            len += 1;
+        }
+
+        len
+    }
+
+    #[test]
+    fn test_double_hash_finds_all_keys() {
+        let mut map: HashMap<u32, u32, 61> =
+            HashMap::new_with_probe_strategy(ProbeStrategy::DoubleHash);
+        assert_eq!(map.probe_strategy(), ProbeStrategy::DoubleHash);
+
+        // Fill the map completely, so every slot must be reachable
+        for key in 0..61 {
+            assert!(map.insert(key * 7, key));
+        }
+        assert!(!map.insert(1000, 0));
+        for key in 0..61 {
+            assert_eq!(map.get(&(key * 7)), Some(&key));
+        }
+
+        for key in (0..61).step_by(2) {
+            assert_eq!(map.remove(&(key * 7)), Some(key));
+        }
+        map.compact_in_place();
+        for key in 0..61 {
+            let expected = (key % 2 == 1).then_some(key);
+            assert_eq!(map.get(&(key * 7)).copied(), expected);
+        }
+
+        // A capacity that isn't prime still reaches every slot
+        let mut map: HashMap<u32, u32, 64> =
+            HashMap::new_with_probe_strategy(ProbeStrategy::DoubleHash);
+        for key in 0..64 {
+            assert!(map.insert(key, key));
+        }
+        assert!((0..64).all(|key| map.get(&key) == Some(&key)));
+    }
+
+    #[test]
+    fn test_double_hash_shorter_probes() {
+        // The byte sums of these keys all fall in 0..=10, so their probes start in one cluster
+        let keys = (0..6).flat_map(|a| (0..6).map(move |b| (a << 8) | b));
+
+        let mut linear: HashMap<u32, (), 61> = HashMap::new();
+        let mut double: HashMap<u32, (), 61> =
+            HashMap::new_with_probe_strategy(ProbeStrategy::DoubleHash);
+        for key in keys.clone() {
+            assert!(linear.insert(key, ()));
+            assert!(double.insert(key, ()));
+        }
+
+        let linear_total: usize = keys.clone().map(|key| probe_len(&linear, &key)).sum();
+        let double_total: usize = keys.clone().map(|key| probe_len(&double, &key)).sum();
+        assert!(
+            double_total < linear_total,
+            "double hashing probed {} slots but linear probing probed {}",
+            double_total,
+            linear_total
+        );
+    }
+
+    #[test]
+    fn test_double_hash_insert_const() {
+        let mut const_inserted: HashMap<u32, u32, 13> =
+            HashMap::new_with_probe_strategy(ProbeStrategy::DoubleHash);
+        let mut inserted: HashMap<u32, u32, 13> =
+            HashMap::new_with_probe_strategy(ProbeStrategy::DoubleHash);
+
+        for key in [0, 13, 26, 1, 14, 256] {
+            assert_eq!(const_inserted.insert_const(key, key), Ok(()));
+            assert!(inserted.insert(key, key));
+        }
+        assert!(const_inserted.entries == inserted.entries);
+    }
+
+    #[test]
+    fn test_repack_keeps_probe_strategy() {
+        let mut map: HashMap<u32, u32, 8> =
+            HashMap::new_with_probe_strategy(ProbeStrategy::DoubleHash);
+        map.insert(1, 1);
+        map.insert(9, 9);
+
+        let map = map
+            .repack_into::<5>()
+            .unwrap_or_else(|_| panic!("Failed to repack"));
+        assert_eq!(map.probe_strategy(), ProbeStrategy::DoubleHash);
+        assert_eq!(map.get(&9), Some(&9));
+    }
+
+    #[test]
+    fn test_repack_keeps_auto_compact() {
+        let mut map: HashMap<u32, u32, 16> = HashMap::new_auto_compact();
+        map.insert(1, 1);
+
+        let mut map = map
+            .repack_into::<8>()
+            .unwrap_or_else(|_| panic!("Failed to repack"));
+        assert!(map.auto_compact);
+
+        // Churn through keys, so tombstones would pile up if compaction had been turned off
+        for i in 2..100 {
+            map.insert(i, i);
+            assert_eq!(map.remove(&i), Some(i));
+            assert!(map.tombstone_count() <= 8 / 4 + 1);
+        }
+        assert_eq!(map.get(&1), Some(&1));
+    }
+
+    #[test]
+    fn test_probe_wraps_around() {
+        // 1, 256 and 65536 all have a byte sum of 1, so with these seeds they all hash to the last
+        // slot and the later ones have to wrap around to the start of the backing array
+        let mut pow2 = HashMap::<u32, u32, 16, _>::new_with_hasher(BuildDefaultHasher::<14> {});
+        let mut non_pow2 = HashMap::<u32, u32, 50, _>::new_with_hasher(BuildDefaultHasher::<48> {});
+
+        for key in [1, 256, 65536] {
+            assert!(pow2.insert(key, key));
+            assert!(non_pow2.insert(key, key));
+        }
+
+        assert_eq!(pow2.entries[15], HashMapEntry::Occupied(1, 1));
+        assert_eq!(pow2.entries[0], HashMapEntry::Occupied(256, 256));
+        assert_eq!(pow2.entries[1], HashMapEntry::Occupied(65536, 65536));
+        assert_eq!(non_pow2.entries[49], HashMapEntry::Occupied(1, 1));
+        assert_eq!(non_pow2.entries[0], HashMapEntry::Occupied(256, 256));
+        assert_eq!(non_pow2.entries[1], HashMapEntry::Occupied(65536, 65536));
+
+        assert_eq!(pow2.remove(&256), Some(256));
+        assert_eq!(non_pow2.remove(&256), Some(256));
+        assert_eq!(pow2.get(&65536), Some(&65536));
+        assert_eq!(non_pow2.get(&65536), Some(&65536));
+    }
 }
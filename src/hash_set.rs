@@ -1,9 +1,11 @@
 use core::{
-    hash::{BuildHasher, Hash, Hasher},
+    hash::{BuildHasher, Hash},
     mem,
 };
 
-use crate::hasher::BuildDefaultHasher;
+#[cfg(feature = "_internal_debug")]
+use crate::hasher::ProbeSlotState;
+use crate::hasher::{BuildDefaultHasher, wrap_index};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum HashSetEntry<T>
@@ -27,7 +29,7 @@ where
 
     pub fn as_ref(&self) -> HashSetEntry<&T> {
         match self {
-            HashSetEntry::Occupied(elem) => HashSetEntry::Occupied(&elem),
+            HashSetEntry::Occupied(elem) => HashSetEntry::Occupied(elem),
             HashSetEntry::Empty => HashSetEntry::Empty,
             HashSetEntry::Deleted => HashSetEntry::Deleted,
         }
@@ -62,6 +64,10 @@ where
     arr: [HashSetEntry<T>; N],
     len: usize,
     hasher: H,
+    // The number of Deleted entries in the backing array
+    tombstones: usize,
+    // Whether to rehash in place once the tombstones pass a threshold
+    auto_compact: bool,
 }
 
 impl<T, const N: usize> HashSet<T, N>
@@ -69,11 +75,84 @@ where
     T: Hash + Eq,
 {
     pub const fn new() -> Self {
+        assert!(N > 0, "capacity N must be greater than 0");
+
         Self {
             arr: [const { HashSetEntry::Empty }; N],
             len: 0,
             hasher: BuildDefaultHasher {},
+            tombstones: 0,
+            auto_compact: false,
+        }
+    }
+
+    /// Create an empty set that uses the default hasher with a custom seed. Unlike
+    /// `new_with_hasher` this is a const fn, so it can be used to initialize statics
+    pub const fn with_seed_const<const SEED: u64>() -> HashSet<T, N, BuildDefaultHasher<SEED>> {
+        assert!(N > 0, "capacity N must be greater than 0");
+
+        HashSet {
+            arr: [const { HashSetEntry::Empty }; N],
+            len: 0,
+            hasher: BuildDefaultHasher {},
+            tombstones: 0,
+            auto_compact: false,
+        }
+    }
+
+    /// Create a set that automatically compacts itself. See `new_auto_compact_with_hasher`
+    pub fn new_auto_compact() -> Self {
+        Self::new_auto_compact_with_hasher(BuildDefaultHasher {})
+    }
+
+    /// Build a set from elements, also returning the number of duplicate elements that were
+    /// dropped. Panics if there are more than `N` distinct elements
+    pub fn from_iter_counting<I>(iter: I) -> (Self, usize)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = Self::new();
+        let mut duplicates = 0;
+
+        for elem in iter {
+            if set.contains(&elem) {
+                duplicates += 1;
+            } else if !set.insert(elem) {
+                panic!("Attempt to add element to full HashSet");
+            }
         }
+
+        (set, duplicates)
+    }
+}
+
+impl<T, const N: usize> Default for HashSet<T, N>
+where
+    T: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for HashSet<T, N>
+where
+    T: Hash + Eq,
+{
+    /// Build a set from elements, ignoring duplicates. Panics if there are more than `N` distinct
+    /// elements
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        for elem in iter {
+            if set.len >= N && !set.contains(&elem) {
+                panic!("Attempt to add element to full HashSet");
+            }
+
+            set.insert(elem);
+        }
+
+        set
     }
 }
 
@@ -83,17 +162,40 @@ where
     H: BuildHasher,
 {
     pub fn new_with_hasher(hasher: H) -> Self {
+        assert!(N > 0, "capacity N must be greater than 0");
+
         Self {
             arr: [const { HashSetEntry::Empty }; N],
             len: 0,
-            hasher: hasher,
+            hasher,
+            tombstones: 0,
+            auto_compact: false,
+        }
+    }
+
+    /// Create a set that automatically compacts itself. Removing elements leaves tombstones in the
+    /// backing array, which make probing slower. When more than `N / 4` tombstones have built up,
+    /// the next insert first rehashes the table in place to clear them. A rehash costs O(N), but
+    /// it can happen at most once every `N / 4` removals, so the amortized cost per removal is
+    /// O(1)
+    pub fn new_auto_compact_with_hasher(hasher: H) -> Self {
+        Self {
+            auto_compact: true,
+            ..Self::new_with_hasher(hasher)
         }
     }
 
     pub fn insert(&mut self, elem: T) -> bool {
+        if self.auto_compact && self.tombstones > N / 4 {
+            self.rehash_in_place();
+        }
+
         if let Some(spot) = self.probe_for_available_spot(&elem) {
+            if let HashSetEntry::Deleted = self.arr[spot] {
+                self.tombstones -= 1;
+            }
             self.arr[spot] = HashSetEntry::Occupied(elem);
-            self.len += 1;
+            self.inc_len();
             true
         } else {
             false
@@ -104,13 +206,82 @@ where
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn remove(&mut self, elem: &T) -> Option<T> {
         let spot = self.probe_for_existing_spot(elem)?;
 
-        self.len -= 1;
+        self.dec_len();
+        self.tombstones += 1;
         self.arr[spot].take().into()
     }
 
+    /// Remove every element yielded by `iter` that is in the set, returning how many were removed
+    pub fn remove_all<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut removed = 0;
+
+        for elem in iter {
+            if self.remove(&elem).is_some() {
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
+    /// The number of deleted markers (tombstones) left in the backing array by removals
+    pub fn tombstone_count(&self) -> usize {
+        self.tombstones
+    }
+
+    /// Remove all tombstones by rehashing the set in place, keeping every element. Each element is
+    /// moved to the earliest free spot on its probe path
+    pub fn clear_tombstones(&mut self) {
+        if self.tombstones > 0 {
+            self.rehash_in_place();
+        }
+    }
+
+    /// Remove an element from the set and return the element that was stored. This is the same as
+    /// `remove`, and is useful when the stored element carries data beyond what is compared
+    pub fn take(&mut self, elem: &T) -> Option<T> {
+        self.remove(elem)
+    }
+
+    /// Remove all elements for which `f` returns false
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_count(f);
+    }
+
+    /// Remove all elements for which `f` returns false, returning the number of elements removed
+    pub fn retain_count<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut removed = 0;
+
+        for entry in self.arr.iter_mut() {
+            if let HashSetEntry::Occupied(elem) = entry
+                && !f(elem)
+            {
+                entry.take();
+                removed += 1;
+            }
+        }
+
+        self.len -= removed;
+        self.tombstones += removed;
+        removed
+    }
+
     pub fn contains(&self, elem: &T) -> bool {
         self.probe_for_existing_spot(elem).is_some()
     }
@@ -121,11 +292,116 @@ where
         self.arr[spot].as_ref().into()
     }
 
-    fn hash_element(&self, elem: &T) -> u64 {
-        let mut hasher = self.hasher.build_hasher();
-        elem.hash(&mut hasher);
+    /// Iterate over the elements that are in exactly one of `self` and `other`. Elements only in
+    /// `self` come first
+    pub fn symmetric_difference<'a, const M: usize>(
+        &'a self,
+        other: &'a HashSet<T, M, H>,
+    ) -> impl Iterator<Item = &'a T> {
+        let only_self = self.elements().filter(|elem| !other.contains(elem));
+        let only_other = other.elements().filter(|elem| !self.contains(elem));
+
+        only_self.chain(only_other)
+    }
+
+    /// Iterate over the elements in backing-array order
+    fn elements(&self) -> impl Iterator<Item = &T> {
+        self.arr
+            .iter()
+            .filter_map(|entry| Option::<&T>::from(entry.as_ref()))
+    }
+
+    /// Rebuild the table in place, clearing all tombstones and moving every element to the
+    /// earliest free spot on its probe path
+    fn rehash_in_place(&mut self) {
+        // pending[i] is true while the element at i still needs to be moved to its final spot
+        let mut pending = [false; N];
+
+        for (i, entry) in self.arr.iter_mut().enumerate() {
+            match entry {
+                HashSetEntry::Occupied(_) => pending[i] = true,
+                HashSetEntry::Deleted => *entry = HashSetEntry::Empty,
+                HashSetEntry::Empty => {}
+            }
+        }
+
+        for i in 0..N {
+            while pending[i] {
+                let mut spot = match &self.arr[i] {
+                    HashSetEntry::Occupied(elem) => {
+                        wrap_index::<N>(self.hash_element(elem) as usize)
+                    }
+                    _ => panic!("Unexpected non-occupied pending spot {} during rehash", i),
+                };
+
+                // Every spot before the first empty or pending one on the probe path is occupied
+                // by an element that has been placed. Spot i is pending, so this always terminates
+                while !pending[spot] && !matches!(self.arr[spot], HashSetEntry::Empty) {
+                    spot = wrap_index::<N>(spot + 1);
+                }
+
+                if spot == i {
+                    pending[i] = false;
+                } else {
+                    // Place the element, and if it displaced a pending one, process that one next
+                    self.arr.swap(i, spot);
+                    pending[i] = pending[spot];
+                    pending[spot] = false;
+                }
+            }
+        }
+
+        self.tombstones = 0;
+    }
+
+    /// Increment the length, asserting in debug builds that it stays within the capacity
+    fn inc_len(&mut self) {
+        debug_assert!(
+            self.len < N,
+            "Attempt to increment len {} past capacity {}",
+            self.len,
+            N
+        );
+        self.len += 1;
+    }
+
+    /// Decrement the length, asserting in debug builds that it doesn't go below zero
+    fn dec_len(&mut self) {
+        debug_assert!(self.len > 0, "Attempt to decrement len below 0");
+        self.len -= 1;
+    }
 
-        hasher.finish()
+    /// Iterate over the slots visited when looking up `elem`, in order, along with the state of
+    /// each. The sequence ends at the first Empty slot, at the slot holding `elem`, or after every
+    /// slot has been visited
+    #[cfg(feature = "_internal_debug")]
+    pub fn probe_sequence(&self, elem: &T) -> impl Iterator<Item = (usize, ProbeSlotState)> {
+        let start = wrap_index::<N>(self.hash_element(elem) as usize);
+        let mut next = Some(start);
+
+        core::iter::from_fn(move || {
+            let spot = next?;
+            let mut done = false;
+            let state = match &self.arr[spot] {
+                HashSetEntry::Empty => {
+                    done = true;
+                    ProbeSlotState::Empty
+                }
+                HashSetEntry::Deleted => ProbeSlotState::Deleted,
+                HashSetEntry::Occupied(found) => {
+                    done = found == elem;
+                    ProbeSlotState::Occupied
+                }
+            };
+
+            let following = wrap_index::<N>(spot + 1);
+            next = (!done && following != start).then_some(following);
+            Some((spot, state))
+        })
+    }
+
+    fn hash_element(&self, elem: &T) -> u64 {
+        self.hasher.hash_one(elem)
     }
 
     fn probe_for_available_spot(&self, elem: &T) -> Option<usize> {
@@ -134,24 +410,33 @@ where
         }
 
         let hash = self.hash_element(elem);
-        let mut spot = hash as usize % N;
+        let mut spot = wrap_index::<N>(hash as usize);
         let original_spot = spot;
 
+        let mut first_deleted = None;
+
         loop {
             match &self.arr[spot] {
-                HashSetEntry::Empty | HashSetEntry::Deleted => {
-                    return Some(spot);
+                HashSetEntry::Empty => {
+                    return Some(first_deleted.unwrap_or(spot));
+                }
+                HashSetEntry::Deleted => {
+                    // The element may still be further along the probe path, so keep looking
+                    first_deleted.get_or_insert(spot);
+                    spot = wrap_index::<N>(spot + 1);
                 }
                 HashSetEntry::Occupied(el) => {
                     if el == elem {
                         return None;
                     }
-                    spot = (spot + 1) % N;
+                    spot = wrap_index::<N>(spot + 1);
                 }
             }
 
             if spot == original_spot {
-                panic!("Unable to find free spot in HashSet with len < N")
+                return Some(first_deleted.unwrap_or_else(|| {
+                    panic!("Unable to find free spot in HashSet with len < N")
+                }));
             }
         }
     }
@@ -162,7 +447,7 @@ where
         }
 
         let hash = self.hash_element(elem);
-        let mut spot = hash as usize % N;
+        let mut spot = wrap_index::<N>(hash as usize);
         let original_spot = spot;
 
         loop {
@@ -171,13 +456,13 @@ where
                     return None;
                 }
                 HashSetEntry::Deleted => {
-                    spot = (spot + 1) % N;
+                    spot = wrap_index::<N>(spot + 1);
                 }
                 HashSetEntry::Occupied(el) => {
                     if el == elem {
                         return Some(spot);
                     } else {
-                        spot = (spot + 1) % N
+                        spot = wrap_index::<N>(spot + 1)
                     }
                 }
             }
@@ -189,6 +474,29 @@ where
     }
 }
 
+/// Two sets are equal if they contain the same elements. Where the elements sit in the backing
+/// arrays doesn't matter
+impl<T, const N: usize, H> PartialEq for HashSet<T, N, H>
+where
+    T: Hash + Eq,
+    H: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self.arr.iter().all(|entry| match entry {
+                HashSetEntry::Occupied(elem) => other.contains(elem),
+                _ => true,
+            })
+    }
+}
+
+impl<T, const N: usize, H> Eq for HashSet<T, N, H>
+where
+    T: Hash + Eq,
+    H: BuildHasher,
+{
+}
+
 #[macro_export]
 macro_rules! set {
     [$($elem:expr),*] => {{
@@ -201,9 +509,9 @@ macro_rules! set {
 
 #[cfg(test)]
 mod tests {
-    use core::hash::{BuildHasher, Hasher};
+    use core::hash::{BuildHasher, Hash, Hasher};
 
-    use crate::{HashSet, hash_set::HashSetEntry};
+    use crate::{HashSet, hash_set::HashSetEntry, hasher::BuildDefaultHasher};
 
     // A type that always returns a hash of zero, to allow both testing hash collision logic and to
     // directly test the contents of the backing structure in a reproducible way
@@ -370,4 +678,353 @@ mod tests {
         assert_eq!(map.get(&4), Some(&4));
         assert_eq!(map.get(&5), Some(&5));
     }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to increment len 2 past capacity 2")]
+    fn test_inc_len_past_capacity() {
+        let mut set = HashSet::<u32, 2>::new();
+        set.insert(1);
+        set.insert(2);
+
+        set.inc_len();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to decrement len below 0")]
+    fn test_dec_len_below_zero() {
+        let mut set = HashSet::<u32, 2>::new();
+        set.dec_len();
+    }
+
+    #[test]
+    fn test_take() {
+        // An element that is hashed and compared only by its id
+        #[derive(Debug)]
+        struct Named {
+            id: u32,
+            name: &'static str,
+        }
+        impl PartialEq for Named {
+            fn eq(&self, other: &Self) -> bool {
+                self.id == other.id
+            }
+        }
+        impl Eq for Named {}
+        impl Hash for Named {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.id.hash(state);
+            }
+        }
+
+        let mut set: HashSet<Named, 20> =
+            set!(Named { id: 1, name: "one" }, Named { id: 2, name: "two" });
+
+        let taken = set.take(&Named { id: 2, name: "" }).unwrap();
+        assert_eq!(taken.id, 2);
+        assert_eq!(taken.name, "two");
+        assert_eq!(set.len, 1);
+        assert!(set.take(&Named { id: 2, name: "" }).is_none());
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let set: HashSet<u32, 10> = [1, 2, 3, 2, 1].into_iter().collect();
+
+        assert_eq!(set.len, 3);
+        assert!(set.contains(&1));
+        assert!(set.contains(&2));
+        assert!(set.contains(&3));
+
+        let set: HashSet<u32, 2> = [1, 2, 1].into_iter().collect();
+        assert_eq!(set.len, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full HashSet")]
+    fn test_from_iter_full_panic() {
+        let _: HashSet<u32, 2> = [1, 2, 3].into_iter().collect();
+    }
+
+    #[test]
+    fn test_from_iter_counting() {
+        let (set, duplicates) = HashSet::<u32, 10>::from_iter_counting([1, 1, 2, 2, 3]);
+
+        assert_eq!(duplicates, 2);
+        assert_eq!(set.len, 3);
+        assert!(set.contains(&1));
+        assert!(set.contains(&2));
+        assert!(set.contains(&3));
+    }
+
+    #[test]
+    fn test_retain_count() {
+        let mut set: HashSet<u32, 20> = set!(1, 2, 3, 4, 5, 6);
+
+        assert_eq!(set.retain_count(|n| n % 2 == 1), 3);
+        assert_eq!(set.len, 3);
+        assert!(set.contains(&1));
+        assert!(!set.contains(&2));
+        assert!(set.contains(&3));
+        assert!(!set.contains(&4));
+        assert!(set.contains(&5));
+        assert!(!set.contains(&6));
+
+        set.retain(|n| *n != 3);
+        assert_eq!(set.len, 2);
+        assert!(!set.contains(&3));
+    }
+
+    #[test]
+    fn test_rehash_in_place() {
+        let bh = IntCollBuildHasher {};
+        let mut set: HashSet<_, 8, _> = HashSet::new_with_hasher(bh);
+
+        for i in 1..=6 {
+            set.insert(i);
+        }
+        set.remove(&1);
+        set.remove(&2);
+        set.remove(&4);
+
+        assert_eq!(set.tombstone_count(), 3);
+
+        set.rehash_in_place();
+
+        assert_eq!(set.tombstone_count(), 0);
+        assert_eq!(set.arr[0], HashSetEntry::Occupied(3));
+        assert_eq!(set.arr[1], HashSetEntry::Occupied(5));
+        assert_eq!(set.arr[2], HashSetEntry::Occupied(6));
+        for i in 3..8 {
+            assert_eq!(set.arr[i], HashSetEntry::Empty);
+        }
+
+        assert_eq!(set.len, 3);
+        assert!(set.contains(&3));
+        assert!(set.contains(&5));
+        assert!(set.contains(&6));
+    }
+
+    #[test]
+    fn test_auto_compact() {
+        let mut set: HashSet<u32, 16> = HashSet::new_auto_compact();
+
+        for i in 0..200 {
+            set.insert(i);
+            if i >= 8 {
+                assert_eq!(set.remove(&(i - 8)), Some(i - 8));
+            }
+
+            assert!(set.tombstone_count() <= 16 / 4 + 1);
+        }
+
+        assert_eq!(set.len, 8);
+        for i in 192..200 {
+            assert!(set.contains(&i));
+        }
+    }
+
+    #[test]
+    fn test_insert_existing_after_tombstone() {
+        let bh = IntCollBuildHasher {};
+        let mut set: HashSet<_, 50, _> = HashSet::new_with_hasher(bh);
+
+        set.insert(1);
+        set.insert(2);
+        set.remove(&1);
+
+        assert!(!set.insert(2));
+        assert_eq!(set.len, 1);
+        assert_eq!(set.remove(&2), Some(2));
+        assert!(!set.contains(&2));
+    }
+
+    #[test]
+    fn test_power_of_two_capacity() {
+        let mut set: HashSet<u32, 16> = HashSet::new();
+
+        for i in 0..16 {
+            assert!(set.insert(i * 300));
+        }
+        for i in 0..16 {
+            assert!(set.contains(&(i * 300)));
+        }
+        assert!(!set.contains(&1));
+    }
+
+    #[test]
+    #[cfg(feature = "_internal_debug")]
+    fn test_probe_sequence() {
+        use crate::hasher::ProbeSlotState::*;
+
+        let bh = IntCollBuildHasher {};
+        let mut set: HashSet<_, 3, _> = HashSet::new_with_hasher(bh);
+        set.insert(1);
+        set.insert(2);
+        set.insert(3);
+        set.remove(&2);
+
+        assert!(
+            set.probe_sequence(&4)
+                .eq([(0, Occupied), (1, Deleted), (2, Occupied)])
+        );
+        assert!(set.probe_sequence(&1).eq([(0, Occupied)]));
+    }
+
+    #[test]
+    fn test_eq() {
+        let set1: HashSet<u32, 10> = [1, 2, 3].into_iter().collect();
+        let set2: HashSet<u32, 10> = [3, 1, 2].into_iter().collect();
+        let set3: HashSet<u32, 10> = [1, 2, 4].into_iter().collect();
+        let set4: HashSet<u32, 10> = [1, 2].into_iter().collect();
+
+        assert!(set1 == set2);
+        assert!(set1 != set3);
+        assert!(set1 != set4);
+    }
+
+    #[test]
+    fn test_eq_tombstones() {
+        let bh = IntCollBuildHasher {};
+        let mut set1: HashSet<_, 10, _> = HashSet::new_with_hasher(bh);
+        set1.insert(4);
+        set1.insert(1);
+        set1.insert(5);
+        set1.insert(2);
+        set1.remove(&4);
+        set1.remove(&5);
+
+        let bh = IntCollBuildHasher {};
+        let mut set2: HashSet<_, 10, _> = HashSet::new_with_hasher(bh);
+        set2.insert(2);
+        set2.insert(1);
+
+        assert_eq!(set1.tombstone_count(), 2);
+        assert!(set1 == set2);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity N must be greater than 0")]
+    fn test_zero_capacity_panic() {
+        let mut set = HashSet::<u32, 0>::new();
+        set.insert(1);
+    }
+
+    #[test]
+    fn test_symmetric_difference() {
+        let set1: HashSet<u32, 10> = set!(1, 2, 3);
+        let set2: HashSet<u32, 5> = set!(2, 3, 4);
+
+        let mut diff = [0; 2];
+        let mut count = 0;
+        for elem in set1.symmetric_difference(&set2) {
+            diff[count] = *elem;
+            count += 1;
+        }
+        diff.sort();
+
+        assert_eq!(count, 2);
+        assert_eq!(diff, [1, 4]);
+        assert_eq!(set1.symmetric_difference(&set1).count(), 0);
+    }
+
+    #[test]
+    fn test_with_seed_const() {
+        use crate::hasher::BuildDefaultHasher;
+
+        const EMPTY: HashSet<u32, 8, BuildDefaultHasher<7>> = HashSet::with_seed_const::<7>();
+        static STATIC_SET: HashSet<u32, 8, BuildDefaultHasher<7>> = HashSet::with_seed_const();
+
+        assert!(STATIC_SET.is_empty());
+        assert!(!STATIC_SET.contains(&1));
+
+        let mut set = EMPTY;
+        assert!(set.insert(1));
+        assert!(set.insert(2));
+        assert!(set.contains(&1));
+        assert_eq!(set.len(), 2);
+
+        // The seed is added to the hash of every element, so it shifts where each one is stored
+        assert_eq!(set.arr[(7 + 1) % 8], HashSetEntry::Occupied(1));
+    }
+
+    #[test]
+    fn test_clear_tombstones() {
+        let bh = IntCollBuildHasher {};
+        let mut set: HashSet<_, 10, _> = HashSet::new_with_hasher(bh);
+        for i in 0..8 {
+            set.insert(i);
+        }
+        set.remove(&1);
+        set.remove(&4);
+        set.remove(&6);
+        assert_eq!(set.tombstone_count(), 3);
+
+        set.clear_tombstones();
+
+        assert_eq!(set.tombstone_count(), 0);
+        assert_eq!(set.len(), 5);
+        assert!(!set.arr.contains(&HashSetEntry::Deleted));
+        for i in [0, 2, 3, 5, 7] {
+            assert!(set.contains(&i));
+        }
+    }
+
+    #[test]
+    fn test_remove_all() {
+        let mut set: HashSet<u32, 10> = set![1, 2, 3, 4, 5];
+
+        assert_eq!(set.remove_all([2, 4, 6]), 2);
+        assert!(set == set![1, 3, 5]);
+        assert_eq!(set.tombstone_count(), 2);
+
+        // Repeated elements are only removed once
+        assert_eq!(set.remove_all([1, 1]), 1);
+        assert_eq!(set.remove_all([]), 0);
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_pow2_and_non_pow2_capacities() {
+        let mut pow2: HashSet<u32, 16> = HashSet::new();
+        let mut non_pow2: HashSet<u32, 50> = HashSet::new();
+
+        for i in 0..16 {
+            assert!(pow2.insert(i * 1000 + 3));
+        }
+        for i in 0..50 {
+            assert!(non_pow2.insert(i * 1000 + 3));
+        }
+
+        assert!((0..16).all(|i| pow2.contains(&(i * 1000 + 3))));
+        assert!((0..50).all(|i| non_pow2.contains(&(i * 1000 + 3))));
+        assert!(!pow2.contains(&4));
+        assert!(!non_pow2.contains(&4));
+    }
+
+    #[test]
+    fn test_probe_wraps_around() {
+        // 1, 256 and 65536 all have a byte sum of 1, so with these seeds they all hash to the last
+        // slot and the later ones have to wrap around to the start of the backing array
+        let mut pow2 = HashSet::<u32, 16, _>::new_with_hasher(BuildDefaultHasher::<14> {});
+        let mut non_pow2 = HashSet::<u32, 50, _>::new_with_hasher(BuildDefaultHasher::<48> {});
+
+        for elem in [1, 256, 65536] {
+            assert!(pow2.insert(elem));
+            assert!(non_pow2.insert(elem));
+        }
+
+        assert_eq!(pow2.arr[15], HashSetEntry::Occupied(1));
+        assert_eq!(pow2.arr[0], HashSetEntry::Occupied(256));
+        assert_eq!(pow2.arr[1], HashSetEntry::Occupied(65536));
+        assert_eq!(non_pow2.arr[49], HashSetEntry::Occupied(1));
+        assert_eq!(non_pow2.arr[0], HashSetEntry::Occupied(256));
+        assert_eq!(non_pow2.arr[1], HashSetEntry::Occupied(65536));
+
+        assert_eq!(pow2.remove(&256), Some(256));
+        assert_eq!(non_pow2.remove(&256), Some(256));
+        assert!(pow2.contains(&65536));
+        assert!(non_pow2.contains(&65536));
+    }
 }
@@ -22,3 +22,216 @@ impl<const SEED: u64> BuildHasher for BuildDefaultHasher<SEED> {
         Self::Hasher { val: SEED }
     }
 }
+
+impl<const SEED: u64> BuildDefaultHasher<SEED> {
+    /// Compute the same hash as `hash_one` for a value whose `Hash` impl writes exactly `bytes`,
+    /// such as the native-endian bytes of an integer. Usable in const contexts
+    pub(crate) const fn hash_bytes_const(bytes: &[u8]) -> u64 {
+        let mut sum = 0u64;
+        let mut i = 0;
+        while i < bytes.len() {
+            sum += bytes[i] as u64;
+            i += 1;
+        }
+
+        SEED.wrapping_add(sum)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Key types that `HashMap::insert_const` and `const_map!` support. These are the primitive
+/// integers, whose `Hash` impl writes exactly their native-endian bytes and whose equality is
+/// bytewise, so both can be computed in const contexts. This trait is sealed
+///
+/// # Safety
+///
+/// Implementors must have no padding bytes, hash as their native-endian bytes, and compare equal
+/// exactly when their bytes are equal
+pub unsafe trait ConstHashKey: Copy + sealed::Sealed {}
+
+macro_rules! impl_const_hash_key {
+    ($($key:ty),*) => {$(
+        impl sealed::Sealed for $key {}
+        // SAFETY: primitive integers have no padding, hash through `write_*` as their native-endian
+        // bytes, and are equal exactly when their bytes are
+        unsafe impl ConstHashKey for $key {}
+    )*};
+}
+
+impl_const_hash_key!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// View the bytes of a key in a const context
+pub(crate) const fn key_bytes<K>(key: &K) -> &[u8]
+where
+    K: ConstHashKey,
+{
+    // SAFETY: `ConstHashKey` types have no padding, so all `size_of::<K>()` bytes are initialized,
+    // and the slice borrows from `key`
+    unsafe { core::slice::from_raw_parts(key as *const K as *const u8, core::mem::size_of::<K>()) }
+}
+
+/// Compare two byte slices in a const context
+pub(crate) const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// The state of a slot visited while probing a hash type's backing array
+#[cfg(feature = "_internal_debug")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeSlotState {
+    Empty,
+    Deleted,
+    Occupied,
+}
+
+/// How a hash type picks the next slot to try when the slot for a key is taken
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeStrategy {
+    /// Try the following slot each time. Keys with nearby hashes build up into long runs of
+    /// occupied slots (primary clustering), which every probe through them has to walk
+    #[default]
+    Linear,
+    /// Step through the slots by an amount derived from a second hash of the key, so keys whose
+    /// probes start in the same run of slots spread out instead of extending it. The second hash
+    /// is the primary hash passed through a mixing function, since reseeding a weak hasher like
+    /// `BuildDefaultHasher` only shifts its output. The step is always coprime to the capacity so
+    /// every slot is visited, and is never adjusted when the capacity is prime
+    DoubleHash,
+}
+
+/// Get the distance between consecutive slots on the probe path for a key with hash `hash`
+pub(crate) const fn probe_step<const N: usize>(strategy: ProbeStrategy, hash: u64) -> usize {
+    match strategy {
+        ProbeStrategy::Linear => 1,
+        ProbeStrategy::DoubleHash => {
+            if N <= 2 {
+                return 1;
+            }
+
+            let mut step = 1 + (mix(hash) % (N as u64 - 1)) as usize;
+            while gcd(step, N) != 1 {
+                step -= 1;
+            }
+            step
+        }
+    }
+}
+
+/// Scramble the bits of `hash`, using the finalizer of splitmix64
+const fn mix(hash: u64) -> u64 {
+    let mut z = hash;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+const fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let rem = a % b;
+        a = b;
+        b = rem;
+    }
+    a
+}
+
+/// Reduce `index` modulo `N`, used to wrap spots around the backing array of the hash types. When
+/// `N` is a power of two this is a bitmask rather than a division, which matters on targets
+/// without hardware divide. `N` is a constant, so the check is resolved at compile time
+#[inline]
+pub(crate) const fn wrap_index<const N: usize>(index: usize) -> usize {
+    if N.is_power_of_two() {
+        index & (N - 1)
+    } else {
+        index % N
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_index() {
+        // The masked path for powers of two must agree with a real modulo, including for indices
+        // well past N
+        for i in (0..200).chain([1000, 1 << 40, usize::MAX - 1, usize::MAX]) {
+            assert_eq!(wrap_index::<1>(i), 0);
+            assert_eq!(wrap_index::<8>(i), i % 8);
+            assert_eq!(wrap_index::<64>(i), i % 64);
+            assert_eq!(wrap_index::<1024>(i), i % 1024);
+            assert_eq!(wrap_index::<50>(i), i % 50);
+            assert_eq!(wrap_index::<63>(i), i % 63);
+        }
+    }
+
+    #[test]
+    fn test_hash_bytes_const() {
+        for i in [0u32, 1, 255, 256, 0xdead_beef, u32::MAX] {
+            assert_eq!(
+                BuildDefaultHasher::<0>::hash_bytes_const(&i.to_ne_bytes()),
+                BuildDefaultHasher::<0> {}.hash_one(i)
+            );
+            assert_eq!(
+                BuildDefaultHasher::<7>::hash_bytes_const(&i.to_ne_bytes()),
+                BuildDefaultHasher::<7> {}.hash_one(i)
+            );
+        }
+        assert_eq!(
+            BuildDefaultHasher::<0>::hash_bytes_const(key_bytes(&-3i64)),
+            BuildDefaultHasher::<0> {}.hash_one(-3i64)
+        );
+        assert_eq!(key_bytes(&0x0102u16), 0x0102u16.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_bytes_eq() {
+        assert!(bytes_eq(&[], &[]));
+        assert!(bytes_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!bytes_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!bytes_eq(&[1, 2], &[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_probe_step() {
+        for hash in [0, 1, 2, 97, 1 << 40, u64::MAX] {
+            assert_eq!(probe_step::<64>(ProbeStrategy::Linear, hash), 1);
+
+            let step = probe_step::<61>(ProbeStrategy::DoubleHash, hash);
+            assert!((1..61).contains(&step));
+
+            // Steps for a non-prime capacity are adjusted to be coprime with it
+            let step = probe_step::<64>(ProbeStrategy::DoubleHash, hash);
+            assert!((1..64).contains(&step));
+            assert_eq!(step % 2, 1);
+            assert_eq!(
+                gcd(probe_step::<60>(ProbeStrategy::DoubleHash, hash), 60),
+                1
+            );
+        }
+
+        assert_eq!(probe_step::<1>(ProbeStrategy::DoubleHash, 5), 1);
+        assert_eq!(probe_step::<2>(ProbeStrategy::DoubleHash, 5), 1);
+
+        // Nearby hashes get different steps
+        let steps: [usize; 8] =
+            core::array::from_fn(|i| probe_step::<61>(ProbeStrategy::DoubleHash, i as u64));
+        assert!(steps.iter().any(|&step| step != steps[0]));
+    }
+}
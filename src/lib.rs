@@ -1,19 +1,111 @@
 #![no_std]
 
+mod error;
 mod hash_map;
 mod hash_set;
 mod hasher;
 mod list;
+mod lru_cache;
+mod multi_map;
+mod ordered_hash_map;
 mod priority_queue;
 mod queue;
+mod running_median;
 mod searchable_list;
 mod stack;
 
 // Re-exports
+pub use error::CapacityError;
 pub use hash_map::HashMap;
 pub use hash_set::HashSet;
+#[cfg(feature = "_internal_debug")]
+pub use hasher::ProbeSlotState;
+pub use hasher::{BuildDefaultHasher, ConstHashKey, ProbeStrategy};
 pub use list::List;
-pub use priority_queue::PriorityQueue;
+pub use lru_cache::LruCache;
+pub use multi_map::MultiMap;
+pub use ordered_hash_map::OrderedHashMap;
+pub use priority_queue::{Comparator, NaturalOrder, PriorityQueue};
 pub use queue::Queue;
+pub use running_median::RunningMedian;
 pub use searchable_list::SearchableList;
-pub use stack::{Stack, StackIter};
+pub use stack::{Stack, StackIter, StackIterMut};
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cmp::Ordering,
+        hash::{BuildHasher, Hash},
+    };
+
+    use super::*;
+
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    // Fails to compile if any collection stops propagating Send/Sync from its contents
+    fn assert_send_sync<T, U, H, C>()
+    where
+        T: Send + Sync + Ord + Hash,
+        U: Send + Sync,
+        H: Send + Sync + BuildHasher,
+        C: Send + Sync + Comparator<T>,
+    {
+        assert_send::<List<T, 4>>();
+        assert_sync::<List<T, 4>>();
+        assert_send::<Stack<T, 4>>();
+        assert_sync::<Stack<T, 4>>();
+        assert_send::<StackIter<'_, T, 4>>();
+        assert_sync::<StackIter<'_, T, 4>>();
+        assert_send::<StackIterMut<'_, T>>();
+        assert_sync::<StackIterMut<'_, T>>();
+        assert_send::<Queue<T, 4>>();
+        assert_sync::<Queue<T, 4>>();
+        assert_send::<PriorityQueue<T, 4>>();
+        assert_sync::<PriorityQueue<T, 4>>();
+        assert_send::<PriorityQueue<T, 4, C>>();
+        assert_sync::<PriorityQueue<T, 4, C>>();
+        assert_send::<SearchableList<T, 4>>();
+        assert_sync::<SearchableList<T, 4>>();
+        assert_send::<RunningMedian<T, 4>>();
+        assert_sync::<RunningMedian<T, 4>>();
+        assert_send::<HashSet<T, 4>>();
+        assert_sync::<HashSet<T, 4>>();
+        assert_send::<HashSet<T, 4, H>>();
+        assert_sync::<HashSet<T, 4, H>>();
+        assert_send::<HashMap<T, U, 4>>();
+        assert_sync::<HashMap<T, U, 4>>();
+        assert_send::<HashMap<T, U, 4, H>>();
+        assert_sync::<HashMap<T, U, 4, H>>();
+        assert_send::<MultiMap<T, U, 4, 2>>();
+        assert_sync::<MultiMap<T, U, 4, 2>>();
+        assert_send::<MultiMap<T, U, 4, 2, H>>();
+        assert_sync::<MultiMap<T, U, 4, 2, H>>();
+        assert_send::<LruCache<T, U, 4>>();
+        assert_sync::<LruCache<T, U, 4>>();
+        assert_send::<LruCache<T, U, 4, H>>();
+        assert_sync::<LruCache<T, U, 4, H>>();
+        assert_send::<OrderedHashMap<T, U, 4>>();
+        assert_sync::<OrderedHashMap<T, U, 4>>();
+        assert_send::<OrderedHashMap<T, U, 4, H>>();
+        assert_sync::<OrderedHashMap<T, U, 4, H>>();
+        assert_send::<CapacityError>();
+        assert_sync::<CapacityError>();
+        assert_send::<BuildDefaultHasher>();
+        assert_sync::<BuildDefaultHasher>();
+        assert_send::<ProbeStrategy>();
+        assert_sync::<ProbeStrategy>();
+        assert_send::<NaturalOrder>();
+        assert_sync::<NaturalOrder>();
+        #[cfg(feature = "_internal_debug")]
+        {
+            assert_send::<ProbeSlotState>();
+            assert_sync::<ProbeSlotState>();
+        }
+    }
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<u32, &str, BuildDefaultHasher<7>, fn(&u32, &u32) -> Ordering>();
+    }
+}
@@ -1,4 +1,20 @@
-use core::ops::{Index, IndexMut};
+use core::{
+    cmp::Ordering,
+    iter::{Product, Sum},
+    ops::{AddAssign, Bound, Index, IndexMut, RangeBounds},
+};
+
+use crate::CapacityError;
+
+#[cfg(test)]
+extern crate std;
+
+#[cfg(test)]
+std::thread_local! {
+    // Number of slots written by `compact_keeping` on this thread, so tests can check that it
+    // moves each kept element at most once
+    static COMPACT_WRITES: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
 
 pub struct List<T, const N: usize> {
     arr: [Option<T>; N],
@@ -6,6 +22,8 @@ pub struct List<T, const N: usize> {
 }
 
 impl<T, const N: usize> List<T, N> {
+    /// Create an empty list. A capacity `N` of 0 is allowed and gives a list that is always full,
+    /// so every push panics and every pop returns `None`
     pub const fn new() -> Self {
         Self {
             arr: [const { None }; N],
@@ -17,6 +35,21 @@ impl<T, const N: usize> List<T, N> {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Create a full list where the element at each index `i` is `f(i)`
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        Self {
+            arr: core::array::from_fn(|i| Some(f(i))),
+            len: N,
+        }
+    }
+
     /// Push a value to the back of the list
     pub fn push_back(&mut self, elem: T) {
         if self.len >= N {
@@ -24,7 +57,7 @@ impl<T, const N: usize> List<T, N> {
         }
 
         self.arr[self.len] = Some(elem);
-        self.len += 1;
+        self.inc_len();
     }
 
     /// Pops a value from the back of the list
@@ -32,7 +65,7 @@ impl<T, const N: usize> List<T, N> {
         if self.len == 0 {
             None
         } else {
-            self.len -= 1;
+            self.dec_len();
             let val = self.arr[self.len].take().unwrap_or_else(|| {
                 panic!("Unexpected None in backing array at index {}", self.len)
             });
@@ -41,28 +74,53 @@ impl<T, const N: usize> List<T, N> {
         }
     }
 
-    /// Remove an element from a specific position in a list
-    pub fn remove(&mut self, index: usize) -> T {
-        if index >= self.len {
+    /// Insert clones of `items` starting at `index`, shifting the elements after it back in a
+    /// single pass. Panics if `index` is greater than the length or the items don't fit
+    pub fn insert_slice(&mut self, index: usize, items: &[T])
+    where
+        T: Clone,
+    {
+        if index > self.len {
             panic!(
-                "Attempt to remove element at invalid index: {} where len is {}",
+                "Attempt to insert at invalid index: {} where len is {}",
                 index, self.len
             );
         }
+        if self.len + items.len() > N {
+            panic!("Attempt to add element to full list");
+        }
 
-        let elem = self.arr[index]
-            .take()
-            .unwrap_or_else(|| panic!("Unexpected None in backing array at index {}", self.len));
+        let count = items.len();
+        for i in (index..self.len).rev() {
+            self.arr[i + count] = self.arr[i].take();
+        }
+        for (i, item) in items.iter().enumerate() {
+            self.arr[index + i] = Some(item.clone());
+        }
 
-        self.len -= 1;
+        self.len += count;
+    }
 
-        for i in index..self.len {
-            self.arr[i] = self.arr[i + 1].take();
+    /// Remove an element from a specific position in a list
+    pub fn remove(&mut self, index: usize) -> T {
+        if index >= self.len {
+            panic!(
+                "Attempt to remove element at invalid index: {} where len is {}",
+                index, self.len
+            );
         }
 
-        self.arr[self.len] = None;
+        let mut removed = None;
+        self.compact_keeping(|i, elem| {
+            if i == index {
+                removed = Some(elem);
+                None
+            } else {
+                Some(elem)
+            }
+        });
 
-        elem
+        removed.unwrap_or_else(|| panic!("Unexpected None in backing array at index {}", index))
     }
 
     pub fn remove_by<F>(&mut self, f: F) -> Option<T>
@@ -79,36 +137,610 @@ impl<T, const N: usize> List<T, N> {
             }
         }
 
-        let index = spot?;
+        spot.map(|index| self.remove(index))
+    }
 
-        let elem = self.arr[index]
-            .take()
-            .unwrap_or_else(|| panic!("Unexpected None in backing array at index {}", self.len));
+    /// Remove all elements for which `f` returns false, keeping the order of the rest
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_count(f);
+    }
 
-        self.len -= 1;
+    /// Remove all elements for which `f` returns false, keeping the order of the rest. Returns
+    /// the number of elements removed
+    pub fn retain_count<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.compact_keeping(|_, elem| f(&elem).then_some(elem))
+    }
+
+    /// Pass each element and its index to `f` in order, keeping the element if `f` returns it and
+    /// removing it otherwise. The kept elements are shifted down in a single pass. Returns the
+    /// number of elements removed
+    fn compact_keeping<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(usize, T) -> Option<T>,
+    {
+        let mut kept = 0;
+
+        for i in 0..self.len {
+            let elem = self.arr[i].take().unwrap_or_else(|| {
+                panic!("None at unexpected pos: {} when len is {}", i, self.len)
+            });
+
+            if let Some(elem) = f(i, elem) {
+                self.arr[kept] = Some(elem);
+                #[cfg(test)]
+                COMPACT_WRITES.with(|writes| writes.set(writes.get() + 1));
+                kept += 1;
+            }
+        }
+
+        let removed = self.len - kept;
+        self.len = kept;
+        removed
+    }
+
+    /// Remove consecutive elements that `same` considers duplicates, with the same semantics as
+    /// `Vec::dedup_by`. `same` is passed each element and the last element that was kept before
+    /// it, and the element is removed if it returns true. Returns the number of elements removed
+    pub fn dedup_by<F>(&mut self, mut same: F) -> usize
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        if self.len == 0 {
+            return 0;
+        }
+
+        let mut kept = 1;
+        for i in 1..self.len {
+            let mut elem = self.arr[i].take().unwrap_or_else(|| {
+                panic!("None at unexpected pos: {} when len is {}", i, self.len)
+            });
+            let prev = self.arr[kept - 1].as_mut().unwrap_or_else(|| {
+                panic!(
+                    "None at unexpected pos: {} when len is {}",
+                    kept - 1,
+                    self.len
+                )
+            });
 
-        for i in index..self.len {
-            self.arr[i] = self.arr[i + 1].take();
+            if !same(&mut elem, prev) {
+                self.arr[kept] = Some(elem);
+                kept += 1;
+            }
+        }
+
+        let removed = self.len - kept;
+        self.len = kept;
+        removed
+    }
+
+    /// Fill every slot of the list with clones of `value`, so that the list is full. Any existing
+    /// elements are dropped
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.fill_with(|| value.clone());
+    }
+
+    /// Fill every slot of the list with values returned by `f`, so that the list is full. Any
+    /// existing elements are dropped
+    pub fn fill_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        for slot in self.arr.iter_mut() {
+            *slot = Some(f());
+        }
+
+        self.len = N;
+    }
+
+    /// Apply `f` to every element in place, in order
+    pub fn map_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        for i in 0..self.len {
+            f(&mut self[i]);
+        }
+    }
+
+    /// Binary search the list for `x`, with the same semantics as `slice::binary_search`. The list
+    /// must already be sorted, otherwise the result is unspecified
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|elem| elem.cmp(x))
+    }
+
+    /// Binary search the list with a comparator function, with the same semantics as
+    /// `slice::binary_search_by`. The list must already be sorted consistently with `f`
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        self.arr[..self.len].binary_search_by(|elem| {
+            f(elem
+                .as_ref()
+                .unwrap_or_else(|| panic!("Unexpected None in backing array of List")))
+        })
+    }
+
+    /// Find the index of the first element for which `pred` returns false, with the same semantics
+    /// as `slice::partition_point`. The list must already be partitioned by `pred`
+    pub fn partition_point<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.arr[..self.len].partition_point(|elem| {
+            pred(
+                elem.as_ref()
+                    .unwrap_or_else(|| panic!("Unexpected None in backing array of List")),
+            )
+        })
+    }
+
+    /// Rotate the list in place so that the element at index `mid` becomes the first element
+    pub fn rotate_left(&mut self, mid: usize) {
+        if mid > self.len {
+            panic!(
+                "Attempt to rotate list by {} where len is {}",
+                mid, self.len
+            );
+        }
+
+        self.arr[..self.len].rotate_left(mid);
+    }
+
+    /// Rotate the list in place so that the last `k` elements move to the front
+    pub fn rotate_right(&mut self, k: usize) {
+        if k > self.len {
+            panic!("Attempt to rotate list by {} where len is {}", k, self.len);
+        }
+
+        self.arr[..self.len].rotate_right(k);
+    }
+
+    /// Remove the elements in `range` from the list, returning them in order through an iterator.
+    /// The elements after the range are shifted down to close the gap when the iterator is
+    /// dropped, and any elements in the range that weren't consumed are dropped with it
+    pub fn drain_range<R>(&mut self, range: R) -> ListDrain<'_, T, N>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = self.resolve_range(&range, "drain");
+
+        // Until the drain is dropped only the elements before the range are considered part of
+        // the list, so the list stays valid if the drain is leaked
+        let tail_end = self.len;
+        self.len = start;
+
+        ListDrain {
+            base: self,
+            index: start,
+            end,
+            tail_end,
+        }
+    }
+
+    /// Append clones of the elements in `range` to the back of the list. Panics if the range is out
+    /// of bounds or the cloned elements don't fit
+    pub fn extend_from_within<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+        T: Clone,
+    {
+        let (start, end) = self.resolve_range(&range, "extend from");
+        if self.len + (end - start) > N {
+            panic!("Attempt to add element to full list");
+        }
+
+        for i in start..end {
+            let elem = self.arr[i]
+                .clone()
+                .unwrap_or_else(|| panic!("Unexpected None in backing array at index {}", i));
+            self.push_back(elem);
+        }
+    }
+
+    /// Keep only the first `n` elements, dropping the rest. Does nothing if the list has `n` or
+    /// fewer elements
+    pub fn keep_prefix(&mut self, n: usize) {
+        if n < self.len {
+            self.drain_range(n..);
         }
+    }
+
+    /// Keep only the last `n` elements, shifting them to the front and dropping the rest. Does
+    /// nothing if the list has `n` or fewer elements
+    pub fn keep_suffix(&mut self, n: usize) {
+        if n < self.len {
+            self.drain_range(..self.len - n);
+        }
+    }
 
-        self.arr[self.len] = None;
+    /// Remove every element from the list in order, passing each one to `f`
+    pub fn drain_with<F>(&mut self, f: F)
+    where
+        F: FnMut(T),
+    {
+        self.drain_range(..).for_each(f);
+    }
+
+    /// Call `f` on each element in order, stopping at the first error and returning it
+    pub fn try_for_each<E, F>(&self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<(), E>,
+    {
+        self.iter().try_for_each(f)
+    }
+
+    /// Iterate over the elements in order, along with the index each one can be accessed at
+    pub fn indexed(&self) -> impl ExactSizeIterator<Item = (usize, &T)> {
+        (0..self.len).map(|i| (i, &self[i]))
+    }
+
+    /// Sum all of the elements in the list
+    pub fn sum(&self) -> T
+    where
+        T: Sum + Copy,
+    {
+        self.iter().copied().sum()
+    }
 
-        Some(elem)
+    /// Multiply all of the elements in the list
+    pub fn product(&self) -> T
+    where
+        T: Product + Copy,
+    {
+        self.iter().copied().product()
     }
 
     pub fn iter(&self) -> ListIter<'_, T, N> {
         ListIter {
             base: self,
             index: 0,
+            end: self.len,
+        }
+    }
+
+    /// Split the list at `mid`, returning iterators over the elements before and after it. Panics
+    /// if `mid` is greater than the length
+    pub fn split_at(&self, mid: usize) -> (ListIter<'_, T, N>, ListIter<'_, T, N>) {
+        if mid > self.len {
+            panic!("Attempt to split list at {} where len is {}", mid, self.len);
+        }
+
+        (
+            ListIter {
+                base: self,
+                index: 0,
+                end: mid,
+            },
+            ListIter {
+                base: self,
+                index: mid,
+                end: self.len,
+            },
+        )
+    }
+
+    /// Split the list at `mid`, returning mutable iterators over the elements before and after it.
+    /// Panics if `mid` is greater than the length
+    pub fn split_at_mut(
+        &mut self,
+        mid: usize,
+    ) -> (
+        impl Iterator<Item = &mut T> + '_,
+        impl Iterator<Item = &mut T> + '_,
+    ) {
+        if mid > self.len {
+            panic!("Attempt to split list at {} where len is {}", mid, self.len);
+        }
+
+        let (left, right) = self.arr[..self.len].split_at_mut(mid);
+        (
+            left.iter_mut().map(Self::unwrap_mut),
+            right.iter_mut().map(Self::unwrap_mut),
+        )
+    }
+
+    /// Iterate over non-overlapping chunks of `size` elements, starting from the back of the list.
+    /// Each chunk is itself an iterator over its elements in order. The last chunk yielded holds
+    /// the first `len % size` elements if the length isn't a multiple of `size`. Panics if `size`
+    /// is 0
+    pub fn rchunks(&self, size: usize) -> impl Iterator<Item = ListIter<'_, T, N>> {
+        if size == 0 {
+            panic!("Attempt to get chunks of size 0");
+        }
+
+        (0..self.len.div_ceil(size)).map(move |i| {
+            let end = self.len - i * size;
+            ListIter {
+                base: self,
+                index: end.saturating_sub(size),
+                end,
+            }
+        })
+    }
+
+    /// Iterate over all overlapping windows of `size` consecutive elements, in order. Each window
+    /// is an iterator over its elements. Panics if `size` is 0
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = ListIter<'_, T, N>> {
+        if size == 0 {
+            panic!("Attempt to get windows of size 0");
+        }
+
+        (0..(self.len + 1).saturating_sub(size)).map(move |start| ListIter {
+            base: self,
+            index: start,
+            end: start + size,
+        })
+    }
+
+    /// Copy the list into a full array, with clones of the elements at the start and clones of
+    /// `fill` in every slot after them
+    pub fn to_array_or(&self, fill: T) -> [T; N]
+    where
+        T: Clone,
+    {
+        core::array::from_fn(|i| {
+            if i < self.len {
+                self[i].clone()
+            } else {
+                fill.clone()
+            }
+        })
+    }
+
+    /// Move the elements into a list with a capacity of `M`, returning `Err(self)` if they don't
+    /// fit. Element order is preserved
+    pub fn to_capacity<const M: usize>(mut self) -> Result<List<T, M>, Self> {
+        if self.len > M {
+            return Err(self);
+        }
+
+        let mut list = List::new();
+        for i in 0..self.len {
+            list.arr[i] = self.arr[i].take();
+        }
+        list.len = self.len;
+
+        Ok(list)
+    }
+
+    /// Get the live region of the backing array, where every slot is `Some`. The elements are
+    /// stored as `Option<T>`, so there is no `&[T]` to borrow, which is why methods like
+    /// `split_at`, `windows` and `rchunks` yield iterators instead of slices. This is the closest
+    /// slice view and supports slice algorithms that can work on the wrapped elements. There is no
+    /// mutable version, since writing `None` into the live region would leave a hole in the list
+    pub fn as_option_slice(&self) -> &[Option<T>] {
+        &self.arr[..self.len]
+    }
+
+    /// Get the unused slots of the backing array, after the last element. Elements written here
+    /// become part of the list once the length is updated with `set_len`
+    pub fn spare_capacity_mut(&mut self) -> &mut [Option<T>] {
+        &mut self.arr[self.len..]
+    }
+
+    /// Set the length of the list directly, usually after writing elements through
+    /// `spare_capacity_mut`
+    ///
+    /// # Safety
+    ///
+    /// `new_len` must be at most `N`, every slot before `new_len` must be `Some`, and every slot
+    /// from `new_len` onwards must be `None`. Other methods rely on this and may panic or lose
+    /// elements if it doesn't hold
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(
+            new_len <= N,
+            "Attempt to set len {} past capacity {}",
+            new_len,
+            N
+        );
+        debug_assert!(self.arr[..new_len].iter().all(Option::is_some));
+        debug_assert!(self.arr[new_len..].iter().all(Option::is_none));
+
+        self.len = new_len;
+    }
+
+    #[cfg(test)]
+    fn verify_no_holes(&self) {
+        assert!(self.len <= N);
+        for (i, elem) in self.arr.iter().enumerate() {
+            assert_eq!(
+                elem.is_some(),
+                i < self.len,
+                "Hole or leftover element at index {} when len is {}",
+                i,
+                self.len
+            );
+        }
+    }
+
+    fn unwrap_mut(elem: &mut Option<T>) -> &mut T {
+        elem.as_mut()
+            .unwrap_or_else(|| panic!("Unexpected None in backing array of List"))
+    }
+
+    /// Get the start and end indices of `range` within the list. Panics, naming `action`, if the
+    /// range is out of bounds or a bound overflows
+    fn resolve_range<R>(&self, range: &R, action: &str) -> (usize, usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => start.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1),
+            Bound::Excluded(&end) => Some(end),
+            Bound::Unbounded => Some(self.len),
+        };
+
+        match (start, end) {
+            (Some(start), Some(end)) if start <= end && end <= self.len => (start, end),
+            (Some(start), Some(end)) => panic!(
+                "Attempt to {} invalid range {}..{} where len is {}",
+                action, start, end, self.len
+            ),
+            _ => panic!(
+                "Attempt to {} invalid range {:?} where len is {}",
+                action,
+                (range.start_bound(), range.end_bound()),
+                self.len
+            ),
+        }
+    }
+
+    /// Increment the length, asserting in debug builds that it stays within the capacity
+    fn inc_len(&mut self) {
+        debug_assert!(
+            self.len < N,
+            "Attempt to increment len {} past capacity {}",
+            self.len,
+            N
+        );
+        self.len += 1;
+    }
+
+    /// Decrement the length, asserting in debug builds that it doesn't go below zero
+    fn dec_len(&mut self) {
+        debug_assert!(self.len > 0, "Attempt to decrement len below 0");
+        self.len -= 1;
+    }
+}
+
+impl<T, const N: usize> TryFrom<&[T]> for List<T, N>
+where
+    T: Clone,
+{
+    type Error = CapacityError;
+
+    fn try_from(value: &[T]) -> Result<Self, Self::Error> {
+        if value.len() > N {
+            return Err(CapacityError {
+                capacity: N,
+                required: value.len(),
+            });
+        }
+
+        let mut list = Self::new();
+        for elem in value {
+            list.push_back(elem.clone());
+        }
+
+        Ok(list)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for List<u8, N> {
+    type Error = CapacityError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_bytes())
+    }
+}
+
+/// Borrow the live region as a slice of `Option<T>`. See `as_option_slice`
+impl<T, const N: usize> AsRef<[Option<T>]> for List<T, N> {
+    fn as_ref(&self) -> &[Option<T>] {
+        self.as_option_slice()
+    }
+}
+
+impl<T, const N: usize> Default for List<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> AddAssign<T> for List<T, N> {
+    /// Push a value to the back of the list
+    fn add_assign(&mut self, rhs: T) {
+        self.push_back(rhs);
+    }
+}
+
+impl<T, const N: usize> AddAssign<&[T]> for List<T, N>
+where
+    T: Clone,
+{
+    /// Push clones of every value in the slice to the back of the list. Panics without modifying
+    /// the list if they don't all fit
+    fn add_assign(&mut self, rhs: &[T]) {
+        if self.len + rhs.len() > N {
+            panic!("Attempt to add element to full list");
+        }
+
+        for elem in rhs {
+            self.push_back(elem.clone());
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for List<T, N>
+where
+    T: Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        for elem in iter {
+            self.push_back(*elem);
         }
     }
 }
 
+impl<T, const N: usize> PartialEq<[T]> for List<T, N>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, const N: usize> PartialEq<&[T]> for List<T, N>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &&[T]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T, const N: usize, const M: usize> PartialEq<[T; M]> for List<T, N>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &[T; M]) -> bool {
+        *self == other[..]
+    }
+}
+
 impl<T, const N: usize> IndexMut<usize> for List<T, N> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        }
         self.arr[index]
             .as_mut()
-            .unwrap_or_else(|| panic!("Invalid index access: {}", index))
+            .unwrap_or_else(|| panic!("Unexpected None at index {} of backing array", index))
     }
 }
 
@@ -116,22 +748,39 @@ impl<T, const N: usize> Index<usize> for List<T, N> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        }
         self.arr[index]
             .as_ref()
-            .unwrap_or_else(|| panic!("Invalid index access: {}", index))
+            .unwrap_or_else(|| panic!("Unexpected None at index {} of backing array", index))
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a List<T, N> {
+    type Item = &'a T;
+    type IntoIter = ListIter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
 pub struct ListIter<'a, T, const N: usize> {
     base: &'a List<T, N>,
     index: usize,
+    // One past the last index to yield
+    end: usize,
 }
 
 impl<'a, T, const N: usize> Iterator for ListIter<'a, T, N> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.base.len {
+        if self.index >= self.end {
             None
         } else {
             let elem = &self.base[self.index];
@@ -141,9 +790,77 @@ impl<'a, T, const N: usize> Iterator for ListIter<'a, T, N> {
     }
 }
 
-#[macro_export]
-macro_rules! list {
-    [$($elem:expr),*] => {{
+impl<'a, T, const N: usize> DoubleEndedIterator for ListIter<'a, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(&self.base[self.end])
+        }
+    }
+}
+
+pub struct ListDrain<'a, T, const N: usize> {
+    base: &'a mut List<T, N>,
+    // The next index in the drained range to yield
+    index: usize,
+    // The end of the drained range
+    end: usize,
+    // The end of the elements after the drained range, which need to be shifted down on drop
+    tail_end: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for ListDrain<'a, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            None
+        } else {
+            let elem = self.base.arr[self.index].take().unwrap_or_else(|| {
+                panic!("Unexpected None in backing array at index {}", self.index)
+            });
+            self.index += 1;
+            Some(elem)
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Drop for ListDrain<'a, T, N> {
+    fn drop(&mut self) {
+        // Move the tail down over the drained range first, which moves the unconsumed elements
+        // past the end of the list. The list is then consistent before any of them are dropped,
+        // even if a destructor panics
+        let start = self.base.len;
+        self.base.arr[start..self.tail_end].rotate_left(self.end - start);
+        self.base.len = start + self.tail_end - self.end;
+
+        drop(ClearSlots(&mut self.base.arr[self.base.len..self.tail_end]));
+    }
+}
+
+// Sets every slot to `None` when dropped. If dropping one of the elements panics, the remaining
+// slots are still cleared while unwinding, so no element is left past the end of the list
+struct ClearSlots<'a, T>(&'a mut [Option<T>]);
+
+impl<'a, T> Drop for ClearSlots<'a, T> {
+    fn drop(&mut self) {
+        if self.0.is_empty() {
+            return;
+        }
+
+        let mut rest = ClearSlots(core::mem::take(&mut self.0));
+        while let Some((first, tail)) = core::mem::take(&mut rest.0).split_first_mut() {
+            rest.0 = tail;
+            *first = None;
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! list {
+    [$($elem:expr),*] => {{
         #[allow(unused_mut)]
         let mut list = $crate::List::new();
         $(list.push_back($elem);)*
@@ -153,6 +870,8 @@ macro_rules! list {
 
 #[cfg(test)]
 mod tests {
+    use core::{cell::Cell, panic::AssertUnwindSafe};
+
     use super::*;
 
     #[test]
@@ -229,6 +948,7 @@ mod tests {
 
         assert_eq!(list.arr, exp_backing);
         assert_eq!(list.len, exp_len);
+        list.verify_no_holes();
     }
 
     #[test]
@@ -248,7 +968,7 @@ mod tests {
     #[test]
     fn test_pop_back() {
         let mut list: List<u32, 10> = list![1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
-        let mut exp_arr = list.arr.clone();
+        let mut exp_arr = list.arr;
         let mut exp_len = list.len();
 
         assert_eq!(list.arr, exp_arr);
@@ -327,12 +1047,13 @@ mod tests {
         assert_eq!(list.pop_back(), None);
         assert_eq!(list.arr, exp_arr);
         assert_eq!(list.len, exp_len);
+        list.verify_no_holes();
     }
 
     #[test]
     fn test_remove() {
         let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
-        let mut exp_arr = list.arr.clone();
+        let mut exp_arr = list.arr;
 
         list.remove(2);
         exp_arr[2] = Some(4);
@@ -341,12 +1062,13 @@ mod tests {
 
         assert_eq!(list.len, 4);
         assert_eq!(list.arr, exp_arr);
+        list.verify_no_holes();
     }
 
     #[test]
     fn test_remove_by() {
         let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
-        let mut exp_arr = list.arr.clone();
+        let mut exp_arr = list.arr;
 
         list.remove_by(|i| i * i == 9);
         exp_arr[2] = Some(4);
@@ -355,6 +1077,7 @@ mod tests {
 
         assert_eq!(list.len, 4);
         assert_eq!(list.arr, exp_arr);
+        list.verify_no_holes();
     }
 
     #[test]
@@ -372,4 +1095,756 @@ mod tests {
             assert_eq!(i, *n as usize);
         }
     }
+
+    #[test]
+    fn test_fill() {
+        let mut list: List<u32, 5> = list![1, 2];
+        list.fill(0);
+
+        assert_eq!(list.len, 5);
+        assert_eq!(list.arr, [Some(0); 5]);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_fill_with() {
+        let mut list = List::<u32, 5>::new();
+        let mut next = 0;
+        list.fill_with(|| {
+            next += 1;
+            next
+        });
+
+        assert_eq!(list.len, 5);
+        assert_eq!(list.arr, [1, 2, 3, 4, 5].map(Some));
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        let list = List::<u8, 5>::try_from("abc").unwrap();
+        assert_eq!(list.len, 3);
+        assert_eq!(list.arr, [Some(b'a'), Some(b'b'), Some(b'c'), None, None]);
+
+        assert_eq!(
+            List::<u8, 5>::try_from("abcdef").err(),
+            Some(CapacityError {
+                capacity: 5,
+                required: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let list = List::<u32, 5>::try_from(&[1, 2, 3, 4, 5][..]).unwrap();
+        assert_eq!(list.len, 5);
+        assert_eq!(list.arr, [1, 2, 3, 4, 5].map(Some));
+
+        assert!(List::<u32, 5>::try_from(&[1, 2, 3, 4, 5, 6][..]).is_err());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to increment len 2 past capacity 2")]
+    fn test_inc_len_past_capacity() {
+        let mut list = List::<u32, 2>::new();
+        list.push_back(1);
+        list.push_back(2);
+
+        list.inc_len();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to decrement len below 0")]
+    fn test_dec_len_below_zero() {
+        let mut list = List::<u32, 2>::new();
+        list.dec_len();
+    }
+
+    #[test]
+    fn test_binary_search() {
+        let list: List<u32, 10> = list![1, 3, 5, 7, 9];
+
+        assert_eq!(list.binary_search(&1), Ok(0));
+        assert_eq!(list.binary_search(&7), Ok(3));
+        assert_eq!(list.binary_search(&9), Ok(4));
+
+        assert_eq!(list.binary_search(&0), Err(0));
+        assert_eq!(list.binary_search(&4), Err(2));
+        assert_eq!(list.binary_search(&10), Err(5));
+
+        assert_eq!(list.binary_search_by(|e| e.cmp(&5)), Ok(2));
+
+        let empty = List::<u32, 10>::new();
+        assert_eq!(empty.binary_search(&1), Err(0));
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let list = List::<u32, 5>::from_fn(|i| i as u32 * i as u32);
+        assert_eq!(list.len, 5);
+        assert_eq!(list.arr, [0, 1, 4, 9, 16].map(Some));
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut list: List<u32, 5> = list![1, 2, 3, 4];
+        list += 5;
+
+        assert_eq!(list.len, 5);
+        assert_eq!(list.arr, [1, 2, 3, 4, 5].map(Some));
+
+        let mut list: List<u32, 5> = list![1, 2, 3];
+        list += &[6, 7][..];
+
+        assert_eq!(list.len, 5);
+        assert_eq!(list.arr, [1, 2, 3, 6, 7].map(Some));
+        list.verify_no_holes();
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full list")]
+    fn test_add_assign_full_panic() {
+        let mut list: List<u32, 5> = list![1, 2, 3, 4];
+        list += &[6, 7][..];
+    }
+
+    #[test]
+    fn test_to_capacity() {
+        let list: List<u32, 4> = list![1, 2, 3];
+
+        let list: List<u32, 8> = list
+            .to_capacity()
+            .unwrap_or_else(|_| panic!("Expected list to fit"));
+        assert_eq!(list.len, 3);
+        assert_eq!(
+            list.arr,
+            [Some(1), Some(2), Some(3), None, None, None, None, None]
+        );
+
+        let list = list.to_capacity::<2>().err().unwrap();
+        assert_eq!(list.len, 3);
+        assert_eq!(
+            list.arr,
+            [Some(1), Some(2), Some(3), None, None, None, None, None]
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
+
+        list.rotate_left(2);
+        assert_eq!(list.len, 5);
+        assert_eq!(list.arr[..5], [3, 4, 5, 1, 2].map(Some));
+        assert_eq!(list.arr[5..], [None; 5]);
+
+        list.rotate_right(2);
+        assert_eq!(list.arr[..5], [1, 2, 3, 4, 5].map(Some));
+
+        list.rotate_left(5);
+        assert_eq!(list.arr[..5], [1, 2, 3, 4, 5].map(Some));
+        list.verify_no_holes();
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to rotate list by 6 where len is 5")]
+    fn test_rotate_invalid_panic() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
+        list.rotate_left(6);
+    }
+
+    #[test]
+    fn test_drain_range() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
+
+        let mut drain = list.drain_range(1..3);
+        assert_eq!(drain.next(), Some(2));
+        assert_eq!(drain.next(), Some(3));
+        assert_eq!(drain.next(), None);
+        drop(drain);
+
+        assert_eq!(list.len, 3);
+        assert_eq!(list.arr[..3], [1, 4, 5].map(Some));
+        assert_eq!(list.arr[3..], [None; 7]);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_drain_range_unconsumed() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
+
+        let mut drain = list.drain_range(1..=3);
+        assert_eq!(drain.next(), Some(2));
+        drop(drain);
+
+        assert_eq!(list.len, 2);
+        assert_eq!(list.arr[..2], [1, 5].map(Some));
+        assert_eq!(list.arr[2..], [None; 8]);
+
+        list.drain_range(..);
+        assert_eq!(list.len, 0);
+        assert_eq!(list.arr, [None; 10]);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to drain invalid range 3..6 where len is 5")]
+    fn test_drain_range_invalid_panic() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
+        list.drain_range(3..6);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempt to drain invalid range (Included(0), Included(18446744073709551615))"
+    )]
+    fn test_drain_range_inclusive_max_panic() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
+        list.drain_range(0..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Attempt to drain invalid range (Excluded(18446744073709551615), Unbounded)"
+    )]
+    fn test_drain_range_excluded_max_start_panic() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5];
+        list.drain_range((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let list: List<u32, 10> = list![1, 2, 3, 4];
+        assert_eq!(list.sum(), 10);
+        assert_eq!(list.product(), 24);
+
+        let empty = List::<u32, 10>::new();
+        assert_eq!(empty.sum(), 0);
+        assert_eq!(empty.product(), 1);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5, 6];
+        list.retain(|n| n % 2 == 1);
+
+        assert_eq!(list.len, 3);
+        assert_eq!(list.arr[..3], [1, 3, 5].map(Some));
+        assert_eq!(list.arr[3..], [None; 7]);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_retain_count() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5, 6];
+
+        assert_eq!(list.retain_count(|n| n % 2 == 1), 3);
+        assert_eq!(list.len, 3);
+        assert_eq!(list.arr[..3], [1, 3, 5].map(Some));
+
+        assert_eq!(list.retain_count(|_| true), 0);
+        assert_eq!(list.len, 3);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_windows() {
+        let list: List<u32, 10> = list![1, 2, 3];
+        let mut windows = list.windows(2);
+
+        assert!(windows.next().unwrap().copied().eq([1, 2]));
+        assert!(windows.next().unwrap().copied().eq([2, 3]));
+        assert!(windows.next().is_none());
+
+        assert_eq!(list.windows(3).count(), 1);
+        assert_eq!(list.windows(4).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_windows_zero_panic() {
+        let list: List<u32, 10> = list![1, 2, 3];
+        let _ = list.windows(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn test_index_oob_panic() {
+        let list: List<u32, 10> = list![1, 2, 3];
+        let _ = list[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 5")]
+    fn test_index_mut_oob_panic() {
+        let mut list: List<u32, 10> = list![1, 2, 3];
+        list[5] = 0;
+    }
+
+    #[test]
+    fn test_split_at() {
+        let list: List<u32, 10> = list![1, 2, 3, 4];
+
+        let (left, right) = list.split_at(2);
+        assert!(left.copied().eq([1, 2]));
+        assert!(right.copied().eq([3, 4]));
+
+        let (left, right) = list.split_at(0);
+        assert_eq!(left.count(), 0);
+        assert!(right.copied().eq([1, 2, 3, 4]));
+
+        let (left, right) = list.split_at(4);
+        assert!(left.copied().eq([1, 2, 3, 4]));
+        assert_eq!(right.count(), 0);
+    }
+
+    #[test]
+    fn test_split_at_mut() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4];
+
+        let (left, right) = list.split_at_mut(2);
+        left.for_each(|elem| *elem *= 10);
+        right.for_each(|elem| *elem += 1);
+
+        assert_eq!(list.arr[..4], [10, 20, 4, 5].map(Some));
+        list.verify_no_holes();
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to split list at 5 where len is 4")]
+    fn test_split_at_panic() {
+        let list: List<u32, 10> = list![1, 2, 3, 4];
+        let _ = list.split_at(5);
+    }
+
+    #[test]
+    fn test_into_iter_ref() {
+        let list: List<u32, 10> = list![1, 2, 3];
+
+        let mut sum = 0;
+        for (i, elem) in (&list).into_iter().enumerate() {
+            assert_eq!(*elem, i as u32 + 1);
+            sum += elem;
+        }
+        for elem in &list {
+            sum += elem;
+        }
+
+        assert_eq!(sum, 12);
+    }
+
+    #[test]
+    fn test_remove_by_matches_retain() {
+        let mut removed: List<u32, 10> = list![1, 2, 3, 4, 5];
+        let mut retained: List<u32, 10> = list![1, 2, 3, 4, 5];
+
+        assert_eq!(removed.remove_by(|n| *n == 3), Some(3));
+        retained.retain(|n| *n != 3);
+
+        assert_eq!(removed.len, retained.len);
+        assert_eq!(removed.arr, retained.arr);
+        removed.verify_no_holes();
+        retained.verify_no_holes();
+    }
+
+    #[test]
+    fn test_retain_single_pass() {
+        let mut list: List<u32, 10> = list![1, 2, 3, 4, 5, 6, 7, 8];
+        let mut calls = 0;
+        COMPACT_WRITES.with(|writes| writes.set(0));
+
+        let removed = list.retain_count(|n| {
+            calls += 1;
+            n % 3 != 0 && n % 2 != 0
+        });
+
+        // Shifting the tail down after each of the 5 removals would write far more than once per
+        // kept element
+        assert_eq!(removed, 5);
+        assert_eq!(calls, 8);
+        assert_eq!(COMPACT_WRITES.with(|writes| writes.get()), 3);
+        assert_eq!(list.arr[..3], [1, 5, 7].map(Some));
+        assert_eq!(list.arr[3..], [None; 7]);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_extend_copied() {
+        let other: List<u32, 4> = list![1, 2, 3];
+        let mut list: List<u32, 8> = list![0];
+
+        list.extend(other.iter());
+        list.extend(&[4, 5]);
+
+        assert_eq!(list.len, 6);
+        assert_eq!(list.arr[..6], [0, 1, 2, 3, 4, 5].map(Some));
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_spare_capacity_mut() {
+        let mut list: List<u32, 6> = list![1, 2];
+
+        let spare = list.spare_capacity_mut();
+        assert_eq!(spare.len(), 4);
+        spare[0] = Some(3);
+        spare[1] = Some(4);
+
+        unsafe { list.set_len(4) };
+
+        assert_eq!(list.len(), 4);
+        assert!(list.iter().copied().eq([1, 2, 3, 4]));
+        assert_eq!(list.spare_capacity_mut().len(), 2);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_drain_with() {
+        let mut coll: List<u32, 5> = list![1, 2, 3];
+        let mut drained = [0; 3];
+        let mut count = 0;
+
+        coll.drain_with(|elem| {
+            drained[count] = elem;
+            count += 1;
+        });
+
+        assert_eq!(count, 3);
+        assert_eq!(drained, [1, 2, 3]);
+        assert_eq!(coll.len, 0);
+        assert_eq!(coll.arr, [None; 5]);
+        coll.verify_no_holes();
+    }
+
+    #[test]
+    fn test_eq_slice() {
+        let list: List<u32, 10> = list![1, 2, 3];
+
+        assert!(list == [1, 2, 3]);
+        assert!(list == [1, 2, 3][..]);
+        let slice: &[u32] = &[1, 2, 3];
+        assert!(list == slice);
+
+        assert!(list != [1, 2]);
+        assert!(list != [1, 2, 3, 4]);
+        assert!(list != [1, 2, 4][..]);
+        assert!(List::<u32, 10>::new() == [0; 0]);
+    }
+
+    #[test]
+    fn test_insert_slice() {
+        let mut list: List<u32, 6> = list![1, 2, 3];
+
+        list.insert_slice(1, &[8, 9]);
+        assert!(list == [1, 8, 9, 2, 3]);
+
+        list.insert_slice(5, &[7]);
+        assert!(list == [1, 8, 9, 2, 3, 7]);
+
+        list.insert_slice(0, &[]);
+        assert_eq!(list.len, 6);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full list")]
+    fn test_insert_slice_full_panic() {
+        let mut list: List<u32, 4> = list![1, 2, 3];
+        list.insert_slice(1, &[8, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to insert at invalid index: 4 where len is 3")]
+    fn test_insert_slice_index_panic() {
+        let mut list: List<u32, 10> = list![1, 2, 3];
+        list.insert_slice(4, &[8]);
+    }
+
+    #[test]
+    fn test_try_for_each() {
+        let coll: List<u32, 10> = list![1, 2, 3, 4];
+        let mut visited = [0; 4];
+        let mut count = 0;
+
+        let result = coll.try_for_each(|elem| {
+            if *elem == 2 {
+                return Err(*elem * 10);
+            }
+            visited[count] = *elem;
+            count += 1;
+            Ok(())
+        });
+
+        assert_eq!(result, Err(20));
+        assert_eq!(visited[..count], [1]);
+        assert_eq!(coll.try_for_each(|_| Ok::<(), ()>(())), Ok(()));
+    }
+
+    #[test]
+    fn test_dedup_by() {
+        let mut list: List<i32, 10> = list![1, 2, 5, 6, 6, 10, 3];
+
+        let removed = list.dedup_by(|a, b| (*a - *b).abs() <= 1);
+
+        assert_eq!(removed, 3);
+        assert!(list == [1, 5, 10, 3]);
+        assert_eq!(list.arr[4..], [None; 6]);
+
+        let mut empty: List<i32, 10> = List::new();
+        assert_eq!(empty.dedup_by(|a, b| a == b), 0);
+        list.verify_no_holes();
+    }
+
+    #[test]
+    fn test_to_array_or() {
+        let list: List<u32, 5> = list![1, 2, 3];
+        assert_eq!(list.to_array_or(0), [1, 2, 3, 0, 0]);
+
+        let full: List<u32, 2> = list![1, 2];
+        assert_eq!(full.to_array_or(0), [1, 2]);
+    }
+
+    #[test]
+    fn test_indexed() {
+        let coll: List<u32, 10> = list![10, 20, 30];
+
+        let indexed = coll.indexed();
+        assert_eq!(indexed.len(), 3);
+        assert!(indexed.eq([(0, &10), (1, &20), (2, &30)]));
+        assert_eq!(coll.indexed().nth(1), Some((1, &20)));
+    }
+
+    // An element that counts its drops, and panics when dropped if `panics` is set
+    struct DropBomb<'a> {
+        drops: &'a Cell<usize>,
+        panics: bool,
+    }
+    impl Drop for DropBomb<'_> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panics {
+                panic!("DropBomb exploded");
+            }
+        }
+    }
+
+    #[test]
+    fn test_drain_range_panicking_drop() {
+        let drops = Cell::new(0);
+        let mut list: List<DropBomb, 6> = List::new();
+        for i in 0..5 {
+            list.push_back(DropBomb {
+                drops: &drops,
+                panics: i == 2,
+            });
+        }
+
+        // Consume only the first drained element, so the panicking one is dropped by the drain
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut drain = list.drain_range(1..4);
+            drain.next();
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(list.len, 2);
+        list.verify_no_holes();
+
+        // The element after the panicking one is still dropped by the drain, and the kept ones
+        // are dropped exactly once along with the list
+        assert_eq!(drops.get(), 3);
+        drop(list);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn test_no_holes_after_mutations() {
+        let mut list: List<u32, 10> = List::new();
+        list.verify_no_holes();
+
+        for i in 0..8 {
+            list.push_back(i);
+            list.verify_no_holes();
+        }
+
+        list.remove(3);
+        list.verify_no_holes();
+        list.remove_by(|n| *n == 6);
+        list.verify_no_holes();
+        list.retain(|n| n % 2 == 0);
+        list.verify_no_holes();
+        list.insert_slice(1, &[10, 11, 12]);
+        list.verify_no_holes();
+        list.drain_range(2..4).next();
+        list.verify_no_holes();
+        list.dedup_by(|a, b| a == b);
+        list.verify_no_holes();
+        list.rotate_left(1);
+        list.verify_no_holes();
+        list.pop_back();
+        list.verify_no_holes();
+        list.drain_with(|_| {});
+        list.verify_no_holes();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let list: List<u32, 10> = list![1, 2, 3, 4, 5];
+
+        assert_eq!(list.partition_point(|x| *x < 3), 2);
+        assert_eq!(list.partition_point(|_| true), 5);
+        assert_eq!(list.partition_point(|_| false), 0);
+        assert_eq!(List::<u32, 10>::new().partition_point(|_| true), 0);
+    }
+
+    #[test]
+    fn test_extend_from_within() {
+        let mut list: List<u32, 6> = list![1, 2, 3];
+        list.extend_from_within(0..2);
+        assert!(list == [1, 2, 3, 1, 2]);
+
+        list.extend_from_within(4..);
+        assert!(list == [1, 2, 3, 1, 2, 2]);
+
+        list.extend_from_within(2..2);
+        assert_eq!(list.len(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full list")]
+    fn test_extend_from_within_full_panic() {
+        let mut list: List<u32, 4> = list![1, 2, 3];
+        list.extend_from_within(..2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to extend from invalid range 1..4 where len is 3")]
+    fn test_extend_from_within_range_panic() {
+        let mut list: List<u32, 8> = list![1, 2, 3];
+        list.extend_from_within(1..=3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "extend from invalid range (Included(1), Included(18446744073709551615"
+    )]
+    fn test_extend_from_within_inclusive_max_panic() {
+        let mut list: List<u32, 8> = list![1, 2, 3];
+        list.extend_from_within(1..=usize::MAX);
+    }
+
+    #[test]
+    fn test_keep_prefix() {
+        let mut list: List<u32, 8> = list![1, 2, 3, 4, 5];
+        list.keep_prefix(3);
+        assert!(list == [1, 2, 3]);
+
+        list.keep_prefix(10);
+        assert!(list == [1, 2, 3]);
+
+        list.keep_prefix(0);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_keep_suffix() {
+        let mut list: List<u32, 8> = list![1, 2, 3, 4, 5];
+        list.keep_suffix(2);
+        assert!(list == [4, 5]);
+        list.verify_no_holes();
+
+        list.keep_suffix(5);
+        assert!(list == [4, 5]);
+
+        list.keep_suffix(0);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_rchunks() {
+        let list: List<u32, 10> = list![1, 2, 3, 4, 5];
+        let mut chunks = list.rchunks(2);
+
+        assert!(chunks.next().unwrap().copied().eq([4, 5]));
+        assert!(chunks.next().unwrap().copied().eq([2, 3]));
+        assert!(chunks.next().unwrap().copied().eq([1]));
+        assert!(chunks.next().is_none());
+
+        assert_eq!(list.rchunks(5).count(), 1);
+        assert_eq!(list.rchunks(6).count(), 1);
+        assert_eq!(List::<u32, 10>::new().rchunks(2).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to get chunks of size 0")]
+    fn test_rchunks_zero_panic() {
+        let list: List<u32, 10> = list![1, 2, 3];
+        let _ = list.rchunks(0);
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        let list: List<u32, 10> = list![1, 2, 3, 4];
+        assert!(list.iter().rev().copied().eq([4, 3, 2, 1]));
+
+        // Both ends meet in the middle without yielding an element twice
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        let mut list: List<u32, 8> = list![1, 20, 3, 40];
+        list.map_in_place(|elem| *elem = (*elem).min(10));
+        assert!(list == [1, 10, 3, 10]);
+
+        let mut seen = List::<u32, 8>::new();
+        list.map_in_place(|elem| seen.push_back(*elem));
+        assert!(seen == [1, 10, 3, 10]);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut list = List::<u32, 0>::new();
+
+        assert!(list.is_empty());
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.windows(1).count(), 0);
+        assert_eq!(list.rchunks(1).count(), 0);
+        list.keep_suffix(0);
+        list.extend_from_within(..);
+        assert_eq!(list.retain_count(|_| false), 0);
+        assert!(list == [0; 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full list")]
+    fn test_zero_capacity_push_panic() {
+        let mut list = List::<u32, 0>::new();
+        list.push_back(1);
+    }
+
+    #[test]
+    fn test_as_option_slice() {
+        fn checksum(elems: &[Option<u32>]) -> u32 {
+            elems.iter().flatten().sum()
+        }
+
+        let mut list: List<u32, 8> = list![1, 2, 3];
+        assert_eq!(list.as_option_slice(), [Some(1), Some(2), Some(3)]);
+        assert_eq!(checksum(list.as_ref()), 6);
+
+        list.pop_back();
+        assert_eq!(list.as_option_slice(), [Some(1), Some(2)]);
+        assert_eq!(checksum(list.as_ref()), 3);
+        assert!(List::<u32, 8>::new().as_option_slice().is_empty());
+    }
 }
@@ -0,0 +1,220 @@
+use core::hash::{BuildHasher, Hash};
+
+use crate::{HashMap, Queue, hasher::BuildDefaultHasher};
+
+/// A cache holding up to `N` entries, which evicts the least recently used entry to make room for
+/// a new one. Recency is tracked by a queue of keys, ordered from least to most recently used, so
+/// promoting a key costs O(N)
+pub struct LruCache<K, V, const N: usize, H = BuildDefaultHasher>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    map: HashMap<K, V, N, H>,
+    recency: Queue<K, N>,
+}
+
+impl<K, V, const N: usize> LruCache<K, V, N>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self::new_with_hasher(BuildDefaultHasher {})
+    }
+}
+
+impl<K, V, const N: usize> Default for LruCache<K, V, N>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize, H> LruCache<K, V, N, H>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    pub fn new_with_hasher(hasher: H) -> Self {
+        // Every eviction leaves a tombstone in the map, so keep them from building up
+        Self {
+            map: HashMap::new_auto_compact_with_hasher(hasher),
+            recency: Queue::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Get the value for `key` without marking it as recently used
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    /// Remove `key` from the cache, returning its value
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let val = self.map.remove(key)?;
+        self.recency.retain(|k| k != key);
+        Some(val)
+    }
+}
+
+impl<K, V, const N: usize, H> LruCache<K, V, N, H>
+where
+    K: Hash + Eq + Clone,
+    H: BuildHasher,
+{
+    /// Get the value for `key`, marking it as the most recently used entry
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        if !self.map.contains_key(key) {
+            return None;
+        }
+
+        self.promote(key);
+        self.map.get(key)
+    }
+
+    /// Get a mutable reference to the value for `key`, marking it as the most recently used entry
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if !self.map.contains_key(key) {
+            return None;
+        }
+
+        self.promote(key);
+        self.map.get_mut(key)
+    }
+
+    /// Insert a value for `key`, marking it as the most recently used entry. If the key is already
+    /// present its value is replaced and `None` is returned. Otherwise, if the cache is full, the
+    /// least recently used entry is removed and returned to make room
+    pub fn put(&mut self, key: K, val: V) -> Option<(K, V)> {
+        if let Some(existing) = self.map.get_mut(&key) {
+            *existing = val;
+            self.promote(&key);
+            return None;
+        }
+
+        let evicted = if self.map.len() >= N {
+            let oldest = self
+                .recency
+                .pop_front()
+                .unwrap_or_else(|| panic!("Unexpected empty recency queue in full LruCache"));
+            let oldest_val = self
+                .map
+                .remove(&oldest)
+                .unwrap_or_else(|| panic!("Unexpected key in recency queue missing from map"));
+            Some((oldest, oldest_val))
+        } else {
+            None
+        };
+
+        self.recency.push_back(key.clone());
+        if !self.map.insert(key, val) {
+            panic!("Unexpected failure to insert into LruCache with a free spot");
+        }
+
+        evicted
+    }
+
+    /// Move `key` to the most recently used end of the recency queue
+    fn promote(&mut self, key: &K) {
+        self.recency.retain(|k| k != key);
+        self.recency.push_back(key.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_get() {
+        let mut cache: LruCache<u32, char, 3> = LruCache::new();
+
+        assert_eq!(cache.put(1, 'a'), None);
+        assert_eq!(cache.put(2, 'b'), None);
+        assert_eq!(cache.get(&1), Some(&'a'));
+        assert_eq!(cache.get(&4), None);
+
+        assert_eq!(cache.put(2, 'x'), None);
+        assert_eq!(cache.peek(&2), Some(&'x'));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_evicts_least_recent() {
+        let mut cache: LruCache<u32, char, 3> = LruCache::new();
+
+        cache.put(1, 'a');
+        cache.put(2, 'b');
+        cache.put(3, 'c');
+
+        assert_eq!(cache.put(4, 'd'), Some((1, 'a')));
+        assert_eq!(cache.put(5, 'e'), Some((2, 'b')));
+        assert!(!cache.contains_key(&1));
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_get_prevents_eviction() {
+        let mut cache: LruCache<u32, char, 3> = LruCache::new();
+
+        cache.put(1, 'a');
+        cache.put(2, 'b');
+        cache.put(3, 'c');
+
+        // 1 is now the most recently used, so 2 is the oldest untouched key
+        assert_eq!(cache.get(&1), Some(&'a'));
+        assert_eq!(cache.put(4, 'd'), Some((2, 'b')));
+
+        // Updating a key also counts as a use
+        cache.put(3, 'x');
+        assert_eq!(cache.put(5, 'e'), Some((1, 'a')));
+
+        // Peeking doesn't
+        assert_eq!(cache.peek(&4), Some(&'d'));
+        assert_eq!(cache.put(6, 'f'), Some((4, 'd')));
+
+        assert!(cache.contains_key(&3));
+        assert!(cache.contains_key(&5));
+        assert!(cache.contains_key(&6));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut cache: LruCache<u32, char, 2> = LruCache::new();
+
+        cache.put(1, 'a');
+        cache.put(2, 'b');
+        assert_eq!(cache.remove(&1), Some('a'));
+        assert_eq!(cache.remove(&1), None);
+
+        assert_eq!(cache.put(3, 'c'), None);
+        assert_eq!(cache.put(4, 'd'), Some((2, 'b')));
+    }
+
+    #[test]
+    fn test_many_evictions() {
+        // Each eviction leaves a tombstone in the map, which must not stop new keys being found
+        let mut cache: LruCache<u32, u32, 4> = LruCache::new();
+
+        for i in 0..100 {
+            let evicted = cache.put(i, i * 10);
+            assert_eq!(evicted, i.checked_sub(4).map(|old| (old, old * 10)));
+        }
+        for i in 96..100 {
+            assert_eq!(cache.get(&i), Some(&(i * 10)));
+        }
+    }
+}
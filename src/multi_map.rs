@@ -0,0 +1,154 @@
+use core::hash::{BuildHasher, Hash};
+
+use crate::{CapacityError, HashMap, List, hasher::BuildDefaultHasher};
+
+/// A map from each key to a list of up to `M` values, for up to `N` distinct keys. Values under a
+/// key are kept in the order they were inserted
+pub struct MultiMap<K, V, const N: usize, const M: usize, H = BuildDefaultHasher>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    map: HashMap<K, List<V, M>, N, H>,
+}
+
+impl<K, V, const N: usize, const M: usize> MultiMap<K, V, N, M>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl<K, V, const N: usize, const M: usize> Default for MultiMap<K, V, N, M>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize, const M: usize, H> MultiMap<K, V, N, M, H>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    pub fn new_with_hasher(hasher: H) -> Self {
+        Self {
+            map: HashMap::new_with_hasher(hasher),
+        }
+    }
+
+    /// Add `val` to the end of the values for `key`. Returns an error without modifying the map if
+    /// the key already has `M` values (with `capacity` of `M`), or if the key is new and there are
+    /// already `N` keys (with `capacity` of `N`)
+    pub fn insert(&mut self, key: K, val: V) -> Result<(), CapacityError> {
+        if let Some(values) = self.map.get_mut(&key) {
+            if values.len() >= M {
+                return Err(CapacityError {
+                    capacity: M,
+                    required: M + 1,
+                });
+            }
+
+            values.push_back(val);
+            return Ok(());
+        }
+
+        if M == 0 {
+            return Err(CapacityError {
+                capacity: 0,
+                required: 1,
+            });
+        }
+        if !self.map.can_insert(&key) {
+            return Err(CapacityError {
+                capacity: N,
+                required: N + 1,
+            });
+        }
+
+        let mut values = List::new();
+        values.push_back(val);
+        self.map.insert(key, values);
+        Ok(())
+    }
+
+    /// The number of distinct keys in the map
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Remove a key, returning all of its values
+    pub fn remove(&mut self, key: &K) -> Option<List<V, M>> {
+        self.map.remove(key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Get the values for `key`, in the order they were inserted
+    pub fn get(&self, key: &K) -> Option<&List<V, M>> {
+        self.map.get(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get() {
+        let mut map: MultiMap<u32, char, 10, 4> = MultiMap::new();
+
+        assert_eq!(map.insert(1, 'a'), Ok(()));
+        assert_eq!(map.insert(2, 'x'), Ok(()));
+        assert_eq!(map.insert(1, 'b'), Ok(()));
+        assert_eq!(map.insert(1, 'c'), Ok(()));
+
+        assert_eq!(map.len(), 2);
+        assert!(map.get(&1).unwrap().iter().eq(['a', 'b', 'c'].iter()));
+        assert!(map.get(&2).unwrap().iter().eq(['x'].iter()));
+        assert!(map.get(&3).is_none());
+
+        let values = map.remove(&1).unwrap();
+        assert!(values == ['a', 'b', 'c']);
+        assert!(!map.contains_key(&1));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_full() {
+        let mut map: MultiMap<u32, char, 2, 2> = MultiMap::new();
+
+        assert_eq!(map.insert(1, 'a'), Ok(()));
+        assert_eq!(map.insert(1, 'b'), Ok(()));
+        assert_eq!(
+            map.insert(1, 'c'),
+            Err(CapacityError {
+                capacity: 2,
+                required: 3
+            })
+        );
+        assert!(map.get(&1).unwrap().iter().eq(['a', 'b'].iter()));
+
+        assert_eq!(map.insert(2, 'x'), Ok(()));
+        assert_eq!(
+            map.insert(3, 'y'),
+            Err(CapacityError {
+                capacity: 2,
+                required: 3
+            })
+        );
+        assert!(!map.contains_key(&3));
+    }
+}
@@ -0,0 +1,163 @@
+use core::hash::{BuildHasher, Hash};
+
+use crate::{HashMap, List, hasher::BuildDefaultHasher, list::ListIter};
+
+/// A `HashMap` that remembers the order its entries were inserted in, and iterates in that order
+/// rather than in the order of the backing array
+pub struct OrderedHashMap<K, V, const N: usize, H = BuildDefaultHasher>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    map: HashMap<K, V, N, H>,
+    // The spots in the backing array of `map` which are occupied, in insertion order
+    order: List<usize, N>,
+}
+
+impl<K, V, const N: usize> OrderedHashMap<K, V, N>
+where
+    K: Hash + Eq,
+{
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+            order: List::new(),
+        }
+    }
+}
+
+impl<K, V, const N: usize> Default for OrderedHashMap<K, V, N>
+where
+    K: Hash + Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize, H> OrderedHashMap<K, V, N, H>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    pub fn new_with_hasher(hasher: H) -> Self {
+        Self {
+            map: HashMap::new_with_hasher(hasher),
+            order: List::new(),
+        }
+    }
+
+    pub fn insert(&mut self, key: K, val: V) -> bool {
+        if let Some(spot) = self.map.insert_at_spot(key, val) {
+            self.order.push_back(spot);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (spot, val) = self.map.remove_at_spot(key)?;
+
+        self.order.remove_by(|s| *s == spot);
+        Some(val)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.map.get_mut(key)
+    }
+
+    /// Iterate over the entries of the map in the order they were inserted
+    pub fn iter(&self) -> OrderedHashMapIter<'_, K, V, N, H> {
+        OrderedHashMapIter {
+            map: &self.map,
+            order: self.order.iter(),
+        }
+    }
+}
+
+pub struct OrderedHashMapIter<'a, K, V, const N: usize, H>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    map: &'a HashMap<K, V, N, H>,
+    order: ListIter<'a, usize, N>,
+}
+
+impl<'a, K, V, const N: usize, H> Iterator for OrderedHashMapIter<'a, K, V, N, H>
+where
+    K: Hash + Eq,
+    H: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let spot = *self.order.next()?;
+
+        Some(
+            self.map.entry_at_spot(spot).unwrap_or_else(|| {
+                panic!("Unexpected non-occupied spot {} in insertion order", spot)
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insertion_order() {
+        let mut map: OrderedHashMap<u32, char, 50> = OrderedHashMap::new();
+
+        map.insert(30, 'a');
+        map.insert(10, 'b');
+        map.insert(20, 'c');
+
+        let mut iter = map.iter();
+        assert_eq!(iter.next(), Some((&30, &'a')));
+        assert_eq!(iter.next(), Some((&10, &'b')));
+        assert_eq!(iter.next(), Some((&20, &'c')));
+        assert_eq!(iter.next(), None);
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn test_insertion_order_after_remove() {
+        let mut map: OrderedHashMap<u32, char, 50> = OrderedHashMap::new();
+
+        map.insert(30, 'a');
+        map.insert(10, 'b');
+        map.insert(20, 'c');
+
+        assert_eq!(map.remove(&10), Some('b'));
+        assert_eq!(map.remove(&10), None);
+        map.insert(40, 'd');
+        map.insert(10, 'e');
+
+        let mut iter = map.iter();
+        assert_eq!(iter.next(), Some((&30, &'a')));
+        assert_eq!(iter.next(), Some((&20, &'c')));
+        assert_eq!(iter.next(), Some((&40, &'d')));
+        assert_eq!(iter.next(), Some((&10, &'e')));
+        assert_eq!(iter.next(), None);
+        assert_eq!(map.len(), 4);
+    }
+}
@@ -1,21 +1,82 @@
-use core::cmp::Ordering;
+use core::{cmp::Ordering, ops::Index};
 
-pub struct PriorityQueue<T, const N: usize>
+/// Defines the order of the elements in a `PriorityQueue`. `pop` removes the smallest element
+/// according to `compare`. Implemented for any `Fn(&T, &T) -> Ordering`
+pub trait Comparator<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Orders elements by their `Ord` implementation. This is the default comparator
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NaturalOrder;
+
+impl<T> Comparator<T> for NaturalOrder
 where
     T: Ord,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T, F> Comparator<T> for F
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+pub struct PriorityQueue<T, const N: usize, C = NaturalOrder>
+where
+    C: Comparator<T>,
 {
     arr: [Option<T>; N],
     len: usize,
+    cmp: C,
 }
 
 impl<T, const N: usize> PriorityQueue<T, N>
 where
     T: Ord,
 {
+    /// Create an empty queue. A capacity `N` of 0 is allowed and gives a queue that is always full,
+    /// so every insert panics, every pop returns `None`, and `insert_or_evict` rejects everything
     pub const fn new() -> Self {
+        Self::with_comparator(NaturalOrder)
+    }
+}
+
+impl<T, const N: usize, F> PriorityQueue<T, N, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Create a queue ordered by the closure `f` holding the elements of `iter`. Panics if there
+    /// are more than `N` elements
+    pub fn from_iter_by<I>(iter: I, f: F) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut pqueue = Self::with_comparator(f);
+        for elem in iter {
+            pqueue.insert(elem);
+        }
+        pqueue
+    }
+}
+
+impl<T, const N: usize, C> PriorityQueue<T, N, C>
+where
+    C: Comparator<T>,
+{
+    /// Create a queue ordered by `cmp` instead of `Ord`. `pop` removes the smallest element
+    /// according to `cmp`, so `|a, b| b.cmp(a)` pops the largest element first
+    pub const fn with_comparator(cmp: C) -> Self {
         Self {
             arr: [const { None }; N],
             len: 0,
+            cmp,
         }
     }
 
@@ -23,10 +84,24 @@ where
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert an element into the queue. An element is placed before any elements that compare
+    /// equal to it, so equal elements are popped in the order they were inserted (FIFO)
     pub fn insert(&mut self, elem: T) {
-        if self.len >= N {
+        if self.try_insert(elem).is_err() {
             panic!("Attempt to add element to full priority queue");
         }
+    }
+
+    /// Insert an element into the queue like `insert`, but return it in `Err` if the queue is full
+    /// instead of panicking. The queue is left unchanged in that case
+    pub fn try_insert(&mut self, elem: T) -> Result<(), T> {
+        if self.len >= N {
+            return Err(elem);
+        }
 
         let spot = self.search_for_new_spot(&elem, 0, self.len);
 
@@ -35,9 +110,95 @@ where
         }
 
         self.arr[spot] = Some(elem);
-        self.len += 1;
+        self.inc_len();
+        Ok(())
     }
 
+    /// Insert an element, evicting the current minimum if the queue is full. This keeps the `N`
+    /// largest elements seen so far.
+    ///
+    /// If the queue is not full the element is inserted and `None` is returned. If it is full and
+    /// `elem` is larger than the minimum, the minimum is removed and returned and `elem` is
+    /// inserted. Otherwise `elem` is rejected and returned. With a capacity of 0 there is no
+    /// minimum to compare against, so `elem` is always rejected.
+    pub fn insert_or_evict(&mut self, elem: T) -> Option<T> {
+        if N == 0 {
+            return Some(elem);
+        }
+        if self.len < N {
+            self.insert(elem);
+            return None;
+        }
+
+        let min = self.arr[self.len - 1].as_ref().unwrap_or_else(|| {
+            panic!(
+                "Unexpected None at index {} when len is {}",
+                self.len - 1,
+                self.len
+            )
+        });
+
+        if self.cmp.compare(&elem, min) == Ordering::Greater {
+            let evicted = self.pop();
+            self.insert(elem);
+            evicted
+        } else {
+            Some(elem)
+        }
+    }
+
+    /// Feed every element of `iter` through `insert_or_evict`, so the queue ends up holding the `N`
+    /// largest elements of itself and the whole stream. Evicted and rejected elements are dropped
+    pub fn extend_bounded<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.insert_or_evict(elem);
+        }
+    }
+
+    /// Move all elements of `other` into this queue, leaving `other` empty. Both backing arrays are
+    /// already sorted, so they are merged in a single pass. Elements from `other` are treated as
+    /// inserted after the existing elements that compare equal to them. Panics without modifying
+    /// either queue if the combined length is greater than `N`
+    pub fn append<const M: usize>(&mut self, other: &mut PriorityQueue<T, M, C>) {
+        if self.len + other.len > N {
+            panic!("Attempt to add element to full priority queue");
+        }
+
+        // Merge from the back (the smallest elements), so that writing into self.arr never
+        // overwrites an element of self that hasn't been merged yet
+        let mut i = self.len;
+        let mut j = other.len;
+        let mut k = self.len + other.len;
+
+        while j > 0 {
+            let take_self = i > 0
+                && match (&self.arr[i - 1], &other.arr[j - 1]) {
+                    (Some(a), Some(b)) => self.cmp.compare(a, b) != Ordering::Greater,
+                    _ => panic!(
+                        "Unexpected None at index {} or {} while merging priority queues",
+                        i - 1,
+                        j - 1
+                    ),
+                };
+
+            k -= 1;
+            if take_self {
+                i -= 1;
+                self.arr[k] = self.arr[i].take();
+            } else {
+                j -= 1;
+                self.arr[k] = other.arr[j].take();
+            }
+        }
+
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    /// Pop the smallest element from the queue
     pub fn pop(&mut self) -> Option<T> {
         if self.len == 0 {
             None
@@ -50,11 +211,160 @@ where
                 )
             });
 
-            self.len -= 1;
+            self.dec_len();
             Some(elem)
         }
     }
 
+    /// Remove the smallest element and insert `elem`, returning the removed element. This leaves
+    /// the queue in the same state as `pop` followed by `insert`. If the queue is empty, `elem` is
+    /// inserted and `None` is returned
+    pub fn replace_min(&mut self, elem: T) -> Option<T> {
+        if self.len == 0 {
+            self.insert(elem);
+            return None;
+        }
+
+        let last = self.len - 1;
+        let min = self.arr[last].take();
+        let spot = self.spot_in_range(&elem, 0, last);
+
+        for i in (spot..last).rev() {
+            self.arr[i + 1] = self.arr[i].take();
+        }
+
+        self.arr[spot] = Some(elem);
+        min
+    }
+
+    /// Remove the largest element and insert `elem`, returning the removed element. If the queue is
+    /// empty, `elem` is inserted and `None` is returned
+    pub fn replace_max(&mut self, elem: T) -> Option<T> {
+        if self.len == 0 {
+            self.insert(elem);
+            return None;
+        }
+
+        let max = self.arr[0].take();
+        // The spot is in terms of the array with the maximum still at index 0, so the new element
+        // goes just before it once everything before it has been shifted down
+        let spot = self.spot_in_range(&elem, 1, self.len);
+
+        for i in 1..spot {
+            self.arr[i - 1] = self.arr[i].take();
+        }
+
+        self.arr[spot - 1] = Some(elem);
+        max
+    }
+
+    /// Get the number of elements, in ascending order, for which `pred` returns true before the
+    /// first one for which it returns false. Like `slice::partition_point`, the elements must be
+    /// partitioned by `pred`
+    pub fn partition_point<F>(&self, mut pred: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // In the descending backing array the elements matching `pred` are at the end
+        let failing = self.arr[..self.len].partition_point(|elem| match elem {
+            Some(elem) => !pred(elem),
+            None => panic!("Unexpected None in backing array of PriorityQueue"),
+        });
+
+        self.len - failing
+    }
+
+    /// Binary search for `x` in ascending order, with the same result as `slice::binary_search` on
+    /// the sorted elements. `Ok` holds the position of a matching element, which can be passed to
+    /// `peek_at` or used as an index, and `Err` holds the position `x` would be inserted at
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize> {
+        let pos = self.partition_point(|elem| self.cmp.compare(elem, x) == Ordering::Less);
+
+        match self.peek_at(pos) {
+            Some(elem) if self.cmp.compare(elem, x) == Ordering::Equal => Ok(pos),
+            _ => Err(pos),
+        }
+    }
+
+    /// Get the element at position `k` in pop order without removing it, so `peek_at(0)` is the
+    /// next element to be popped (the smallest). Returns `None` if `k` is out of bounds
+    pub fn peek_at(&self, k: usize) -> Option<&T> {
+        if k >= self.len {
+            None
+        } else {
+            self.arr[self.len - 1 - k].as_ref()
+        }
+    }
+
+    /// Iterate over the elements in ascending order without removing them. Since `pop` removes the
+    /// smallest element, the first element yielded is the next to be popped
+    pub fn iter_sorted(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.arr[..self.len].iter().rev().map(|elem| {
+            elem.as_ref()
+                .unwrap_or_else(|| panic!("Unexpected None in backing array of PriorityQueue"))
+        })
+    }
+
+    /// Iterate over the `n` largest elements in descending order, or all elements if there are
+    /// fewer than `n`
+    pub fn nlargest(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.iter_sorted().rev().take(n)
+    }
+
+    /// Iterate over the `n` smallest elements in ascending order, or all elements if there are
+    /// fewer than `n`
+    pub fn nsmallest(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.iter_sorted().take(n)
+    }
+
+    /// Remove all elements for which `f` returns false. The remaining elements are compacted to
+    /// the front of the backing array, keeping their sorted order
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut kept = 0;
+
+        for i in 0..self.len {
+            let elem = self.arr[i].take().unwrap_or_else(|| {
+                panic!("Unexpected None at index {} when len is {}", i, self.len)
+            });
+
+            if f(&elem) {
+                self.arr[kept] = Some(elem);
+                kept += 1;
+            }
+        }
+
+        self.len = kept;
+    }
+
+    /// Increment the length, asserting in debug builds that it stays within the capacity
+    fn inc_len(&mut self) {
+        debug_assert!(
+            self.len < N,
+            "Attempt to increment len {} past capacity {}",
+            self.len,
+            N
+        );
+        self.len += 1;
+    }
+
+    /// Decrement the length, asserting in debug builds that it doesn't go below zero
+    fn dec_len(&mut self) {
+        debug_assert!(self.len > 0, "Attempt to decrement len below 0");
+        self.len -= 1;
+    }
+
+    /// Find the spot for a new element among the elements in `start..end`, which may be empty
+    fn spot_in_range(&self, elem: &T, start: usize, end: usize) -> usize {
+        if start == end {
+            start
+        } else {
+            self.search_for_new_spot(elem, start, end)
+        }
+    }
+
     fn search_for_new_spot(&self, elem: &T, start: usize, end: usize) -> usize {
         let diff = end - start;
 
@@ -71,9 +381,9 @@ where
             let start_e = self.arr[start].as_ref().unwrap_or_else(|| {
                 panic!("Unexpected None at index {} when len {}", start, self.len)
             });
-            match start_e.cmp(elem) {
-                Ordering::Greater | Ordering::Equal => end,
-                Ordering::Less => start,
+            match self.cmp.compare(start_e, elem) {
+                Ordering::Greater => end,
+                Ordering::Less | Ordering::Equal => start,
             }
         } else {
             let midpoint = start + (diff / 2);
@@ -84,16 +394,79 @@ where
                     midpoint, self.len
                 )
             });
-            match mid_e.cmp(elem) {
-                Ordering::Greater | Ordering::Equal => {
-                    self.search_for_new_spot(elem, midpoint, end)
-                }
-                Ordering::Less => self.search_for_new_spot(elem, start, midpoint),
+            match self.cmp.compare(mid_e, elem) {
+                Ordering::Greater => self.search_for_new_spot(elem, midpoint, end),
+                Ordering::Less | Ordering::Equal => self.search_for_new_spot(elem, start, midpoint),
             }
         }
     }
 }
 
+impl<T, const N: usize> Default for PriorityQueue<T, N>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Index the queue in ascending order, so index 0 is the smallest element (the next to be popped)
+/// and index `len - 1` is the largest
+impl<T, const N: usize, C> Index<usize> for PriorityQueue<T, N, C>
+where
+    C: Comparator<T>,
+{
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        }
+
+        let pos = self.len - 1 - index;
+        self.arr[pos]
+            .as_ref()
+            .unwrap_or_else(|| panic!("Unexpected None at index {} of backing array", pos))
+    }
+}
+
+/// Two queues are equal if they hold the same elements, regardless of the order they were
+/// inserted in
+impl<T, const N: usize> PartialEq for PriorityQueue<T, N>
+where
+    T: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter_sorted().eq(other.iter_sorted())
+    }
+}
+
+impl<T, const N: usize> Eq for PriorityQueue<T, N> where T: Ord {}
+
+impl<T, const N: usize> PartialOrd for PriorityQueue<T, N>
+where
+    T: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Queues are compared lexicographically by their elements in ascending order, the order they
+/// would be popped in
+impl<T, const N: usize> Ord for PriorityQueue<T, N>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter_sorted().cmp(other.iter_sorted())
+    }
+}
+
 #[macro_export]
 macro_rules! pqueue {
     [$($elem:expr),*] => {{
@@ -104,10 +477,45 @@ macro_rules! pqueue {
     }};
 }
 
+/// Create a priority queue ordered by a comparator, followed by its elements. `pop` removes the
+/// smallest element according to the comparator
+#[macro_export]
+macro_rules! pqueue_by {
+    ($cmp:expr $(, $elem:expr)* $(,)?) => {
+        $crate::PriorityQueue::from_iter_by([$($elem),*], $cmp)
+    };
+}
+
 #[cfg(test)]
 mod tests {
+    use core::cmp::Ordering;
+
     use crate::PriorityQueue;
 
+    // A task that is ordered only by its priority, with a sequence number to tell equal-priority
+    // tasks apart
+    #[derive(Debug)]
+    struct Task {
+        priority: u32,
+        seq: u32,
+    }
+    impl PartialEq for Task {
+        fn eq(&self, other: &Self) -> bool {
+            self.priority == other.priority
+        }
+    }
+    impl Eq for Task {}
+    impl PartialOrd for Task {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Task {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.priority.cmp(&other.priority)
+        }
+    }
+
     #[test]
     fn test_insert() {
         let mut pqueue = PriorityQueue::<_, 10>::new();
@@ -185,4 +593,429 @@ mod tests {
         assert_eq!(pqueue.arr, exp_arr);
         assert_eq!(pqueue.len, 2);
     }
+
+    #[test]
+    fn test_retain() {
+        let mut pqueue: PriorityQueue<_, 10> = pqueue!(1, 2, 3, 4, 5);
+        pqueue.retain(|e| *e > 2);
+
+        let mut exp_arr = [None; 10];
+        exp_arr[0] = Some(5);
+        exp_arr[1] = Some(4);
+        exp_arr[2] = Some(3);
+
+        assert_eq!(pqueue.arr, exp_arr);
+        assert_eq!(pqueue.len, 3);
+
+        assert_eq!(pqueue.pop(), Some(3));
+        assert_eq!(pqueue.pop(), Some(4));
+        assert_eq!(pqueue.pop(), Some(5));
+        assert_eq!(pqueue.pop(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to increment len 2 past capacity 2")]
+    fn test_inc_len_past_capacity() {
+        let mut pqueue = PriorityQueue::<u32, 2>::new();
+        pqueue.insert(1);
+        pqueue.insert(2);
+
+        pqueue.inc_len();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to decrement len below 0")]
+    fn test_dec_len_below_zero() {
+        let mut pqueue = PriorityQueue::<u32, 2>::new();
+        pqueue.dec_len();
+    }
+
+    #[test]
+    fn test_equal_elements_fifo() {
+        let mut pqueue = PriorityQueue::<Task, 10>::new();
+
+        pqueue.insert(Task {
+            priority: 2,
+            seq: 0,
+        });
+        for seq in 1..5 {
+            pqueue.insert(Task { priority: 1, seq });
+        }
+        pqueue.insert(Task {
+            priority: 0,
+            seq: 5,
+        });
+        pqueue.insert(Task {
+            priority: 1,
+            seq: 6,
+        });
+
+        let mut popped = [(0, 0); 7];
+        for p in popped.iter_mut() {
+            let task = pqueue.pop().unwrap();
+            *p = (task.priority, task.seq);
+        }
+
+        assert_eq!(
+            popped,
+            [(0, 5), (1, 1), (1, 2), (1, 3), (1, 4), (1, 6), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn test_insert_or_evict() {
+        let mut pqueue = PriorityQueue::<u32, 3>::new();
+
+        assert_eq!(pqueue.insert_or_evict(1), None);
+        assert_eq!(pqueue.insert_or_evict(5), None);
+        assert_eq!(pqueue.insert_or_evict(2), None);
+        assert_eq!(pqueue.insert_or_evict(8), Some(1));
+        assert_eq!(pqueue.insert_or_evict(3), Some(2));
+        assert_eq!(pqueue.insert_or_evict(3), Some(3));
+
+        assert_eq!(pqueue.arr, [Some(8), Some(5), Some(3)]);
+        assert_eq!(pqueue.len, 3);
+    }
+
+    #[test]
+    fn test_extend_bounded() {
+        let mut pqueue = PriorityQueue::<u32, 5>::new();
+        pqueue.extend_bounded(0..100);
+
+        assert_eq!(pqueue.len(), 5);
+        assert!(pqueue.iter_sorted().copied().eq(95..=99));
+
+        // Existing elements compete with the stream too
+        let mut pqueue: PriorityQueue<u32, 3> = pqueue![50, 1];
+        pqueue.extend_bounded([3, 7, 2]);
+        assert!(pqueue.iter_sorted().copied().eq([3, 7, 50]));
+    }
+
+    #[test]
+    fn test_iter_sorted() {
+        let pqueue: PriorityQueue<_, 10> = pqueue!(3, 1, 2);
+
+        let mut iter = pqueue.iter_sorted();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+
+        let mut exp_arr = [None; 10];
+        exp_arr[0] = Some(3);
+        exp_arr[1] = Some(2);
+        exp_arr[2] = Some(1);
+
+        assert_eq!(pqueue.arr, exp_arr);
+        assert_eq!(pqueue.len, 3);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut pqueue: PriorityQueue<_, 10> = pqueue!(1, 3, 5);
+        let mut other: PriorityQueue<_, 3> = pqueue!(2, 4, 6);
+
+        pqueue.append(&mut other);
+
+        let mut exp_arr = [None; 10];
+        for (i, n) in [6, 5, 4, 3, 2, 1].into_iter().enumerate() {
+            exp_arr[i] = Some(n);
+        }
+
+        assert_eq!(pqueue.arr, exp_arr);
+        assert_eq!(pqueue.len, 6);
+        assert_eq!(other.arr, [None; 3]);
+        assert_eq!(other.len, 0);
+
+        for n in 1..=6 {
+            assert_eq!(pqueue.pop(), Some(n));
+        }
+        assert_eq!(pqueue.pop(), None);
+    }
+
+    #[test]
+    fn test_append_equal_fifo() {
+        let mut pqueue = PriorityQueue::<Task, 10>::new();
+        pqueue.insert(Task {
+            priority: 1,
+            seq: 0,
+        });
+        let mut other = PriorityQueue::<Task, 10>::new();
+        other.insert(Task {
+            priority: 1,
+            seq: 1,
+        });
+
+        pqueue.append(&mut other);
+
+        assert_eq!(pqueue.pop().map(|t| t.seq), Some(0));
+        assert_eq!(pqueue.pop().map(|t| t.seq), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full priority queue")]
+    fn test_append_full_panic() {
+        let mut pqueue: PriorityQueue<_, 4> = pqueue!(1, 3, 5);
+        let mut other: PriorityQueue<_, 3> = pqueue!(2, 4);
+
+        pqueue.append(&mut other);
+    }
+
+    #[test]
+    fn test_nlargest_nsmallest() {
+        let pqueue: PriorityQueue<_, 10> = pqueue!(5, 3, 1, 4, 2);
+
+        assert!(pqueue.nlargest(2).eq([5, 4].iter()));
+        assert!(pqueue.nsmallest(2).eq([1, 2].iter()));
+
+        assert_eq!(pqueue.nlargest(10).count(), 5);
+        assert_eq!(pqueue.nsmallest(0).count(), 0);
+    }
+
+    #[test]
+    fn test_try_insert() {
+        let mut pqueue: PriorityQueue<_, 3> = PriorityQueue::new();
+
+        assert_eq!(pqueue.try_insert(2), Ok(()));
+        assert_eq!(pqueue.try_insert(3), Ok(()));
+        assert_eq!(pqueue.try_insert(1), Ok(()));
+
+        assert_eq!(pqueue.try_insert(0), Err(0));
+        assert_eq!(pqueue.try_insert(4), Err(4));
+        assert_eq!(pqueue.arr, [Some(3), Some(2), Some(1)]);
+        assert_eq!(pqueue.len, 3);
+    }
+
+    #[test]
+    fn test_peek_at() {
+        let mut pqueue: PriorityQueue<_, 10> = pqueue!(5, 3, 1, 4, 2);
+
+        assert_eq!(pqueue.peek_at(0), Some(&1));
+        assert_eq!(pqueue.peek_at(2), Some(&3));
+        assert_eq!(pqueue.peek_at(4), Some(&5));
+        assert_eq!(pqueue.peek_at(5), None);
+
+        assert_eq!(pqueue.peek_at(0).copied(), pqueue.pop());
+        assert_eq!(pqueue.peek_at(0), Some(&2));
+    }
+
+    #[test]
+    fn test_replace_min() {
+        for elem in 0..7 {
+            let mut replaced: PriorityQueue<_, 10> = pqueue!(5, 3, 1, 4, 2);
+            let mut reference: PriorityQueue<_, 10> = pqueue!(5, 3, 1, 4, 2);
+
+            assert_eq!(replaced.replace_min(elem), Some(1));
+            assert_eq!(reference.pop(), Some(1));
+            reference.insert(elem);
+
+            assert_eq!(replaced.arr, reference.arr);
+            assert_eq!(replaced.len, 5);
+        }
+
+        let mut pqueue: PriorityQueue<_, 10> = PriorityQueue::new();
+        assert_eq!(pqueue.replace_min(3), None);
+        assert_eq!(pqueue.replace_min(4), Some(3));
+        assert_eq!(pqueue.len, 1);
+    }
+
+    #[test]
+    fn test_replace_max() {
+        let mut pqueue: PriorityQueue<_, 10> = pqueue!(5, 3, 1, 4, 2);
+
+        assert_eq!(pqueue.replace_max(0), Some(5));
+        assert!(pqueue.iter_sorted().eq([0, 1, 2, 3, 4].iter()));
+
+        assert_eq!(pqueue.replace_max(6), Some(4));
+        assert!(pqueue.iter_sorted().eq([0, 1, 2, 3, 6].iter()));
+
+        assert_eq!(pqueue.replace_max(2), Some(6));
+        assert!(pqueue.iter_sorted().eq([0, 1, 2, 2, 3].iter()));
+        assert_eq!(pqueue.len, 5);
+
+        let mut pqueue: PriorityQueue<_, 10> = PriorityQueue::new();
+        assert_eq!(pqueue.replace_max(3), None);
+        assert_eq!(pqueue.replace_max(4), Some(3));
+        assert_eq!(pqueue.len, 1);
+    }
+
+    #[test]
+    fn test_replace_ties() {
+        // Replacing keeps the same FIFO order among equal elements as removing and inserting
+        let mut pqueue: PriorityQueue<_, 10> = PriorityQueue::new();
+        pqueue.insert(Task {
+            priority: 1,
+            seq: 0,
+        });
+        pqueue.insert(Task {
+            priority: 2,
+            seq: 1,
+        });
+        pqueue.insert(Task {
+            priority: 3,
+            seq: 2,
+        });
+
+        pqueue.replace_max(Task {
+            priority: 2,
+            seq: 3,
+        });
+        pqueue.replace_min(Task {
+            priority: 2,
+            seq: 4,
+        });
+
+        assert_eq!(pqueue.pop().map(|t| t.seq), Some(1));
+        assert_eq!(pqueue.pop().map(|t| t.seq), Some(3));
+        assert_eq!(pqueue.pop().map(|t| t.seq), Some(4));
+    }
+
+    #[test]
+    fn test_index() {
+        let pqueue: PriorityQueue<_, 10> = pqueue!(3, 1, 2);
+
+        assert_eq!(pqueue[0], 1);
+        assert_eq!(pqueue[1], 2);
+        assert_eq!(pqueue[2], 3);
+        assert_eq!(pqueue.peek_at(0), Some(&pqueue[0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn test_index_oob_panic() {
+        let pqueue: PriorityQueue<_, 10> = pqueue!(3, 1, 2);
+        let _ = pqueue[3];
+    }
+
+    #[test]
+    fn test_eq() {
+        let a: PriorityQueue<u32, 5> = pqueue!(1, 2, 3);
+        let b: PriorityQueue<u32, 5> = pqueue!(3, 2, 1);
+        assert!(a == b);
+
+        let c: PriorityQueue<u32, 5> = pqueue!(1, 2, 3, 3);
+        assert!(a != c);
+        assert!(a != PriorityQueue::new());
+        assert!(PriorityQueue::<u32, 5>::new() == PriorityQueue::new());
+    }
+
+    #[test]
+    fn test_ord() {
+        let a: PriorityQueue<u32, 5> = pqueue!(1, 2, 3);
+
+        // The smallest elements are compared first
+        assert!(a < pqueue!(2, 3));
+        assert!(a > pqueue!(1, 1, 9));
+
+        // A prefix is smaller than the longer sequence
+        assert!(a < pqueue!(1, 2, 3, 4));
+        assert!(a > pqueue!(1, 2));
+        assert!(PriorityQueue::new() < a);
+
+        assert_eq!(a.cmp(&pqueue!(3, 1, 2)), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_pqueue_by() {
+        let mut pqueue: PriorityQueue<u32, 5, _> = pqueue_by!(|a, b| b.cmp(a), 3, 1, 2);
+
+        // The comparator is reversed, so the largest element is the "smallest" and popped first
+        assert_eq!(pqueue.pop(), Some(3));
+        assert_eq!(pqueue.pop(), Some(2));
+        assert_eq!(pqueue.pop(), Some(1));
+        assert_eq!(pqueue.pop(), None);
+
+        let mut pqueue: PriorityQueue<u32, 5, _> = pqueue_by!(|a, b| a.cmp(b), 3, 1, 2);
+        assert_eq!(pqueue.pop(), Some(1));
+        assert_eq!(pqueue.pop(), Some(2));
+        assert_eq!(pqueue.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_with_comparator() {
+        // Order by the last digit only, keeping equal elements FIFO
+        let mut pqueue =
+            PriorityQueue::<u32, 8, _>::with_comparator(|a: &u32, b: &u32| (a % 10).cmp(&(b % 10)));
+        for elem in [21, 13, 11, 32, 3] {
+            pqueue.insert(elem);
+        }
+
+        assert!(pqueue.iter_sorted().copied().eq([21, 11, 32, 13, 3]));
+        assert_eq!(pqueue[0], 21);
+        assert_eq!(pqueue.insert_or_evict(9), None);
+
+        let mut other = PriorityQueue::<u32, 8, _>::with_comparator(pqueue.cmp);
+        other.insert(40);
+        other.insert(5);
+        pqueue.append(&mut other);
+        assert!(
+            pqueue
+                .iter_sorted()
+                .copied()
+                .eq([40, 21, 11, 32, 13, 3, 5, 9])
+        );
+    }
+
+    #[test]
+    fn test_comparator_insert_or_evict() {
+        let mut pqueue: PriorityQueue<u32, 2, _> = pqueue_by!(|a: &u32, b: &u32| b.cmp(a));
+        pqueue.extend_bounded([5, 1, 8, 3]);
+
+        // Under the reversed order the "largest" elements are the smallest numbers
+        assert!(pqueue.iter_sorted().copied().eq([3, 1]));
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut pqueue = PriorityQueue::<u32, 0>::new();
+
+        assert!(pqueue.is_empty());
+        assert_eq!(pqueue.pop(), None);
+        assert_eq!(pqueue.peek_at(0), None);
+        assert_eq!(pqueue.try_insert(1), Err(1));
+        assert_eq!(pqueue.insert_or_evict(5), Some(5));
+        pqueue.extend_bounded(0..10);
+        assert!(pqueue.is_empty());
+        assert_eq!(pqueue.iter_sorted().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add element to full priority queue")]
+    fn test_zero_capacity_insert_panic() {
+        let mut pqueue = PriorityQueue::<u32, 0>::new();
+        pqueue.insert(1);
+    }
+
+    #[test]
+    fn test_binary_search() {
+        let pqueue: PriorityQueue<u32, 8> = pqueue!(3, 1, 2);
+        assert!(pqueue.iter_sorted().eq([1, 2, 3].iter()));
+
+        assert_eq!(pqueue.binary_search(&1), Ok(0));
+        assert_eq!(pqueue.binary_search(&2), Ok(1));
+        assert_eq!(pqueue.binary_search(&3), Ok(2));
+        assert_eq!(pqueue.binary_search(&0), Err(0));
+        assert_eq!(pqueue.binary_search(&4), Err(3));
+        assert_eq!(PriorityQueue::<u32, 8>::new().binary_search(&1), Err(0));
+
+        let pqueue: PriorityQueue<u32, 8> = pqueue!(5, 1, 9, 7);
+        assert_eq!(pqueue.binary_search(&6), Err(2));
+        assert_eq!(pqueue[pqueue.binary_search(&7).unwrap()], 7);
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let pqueue: PriorityQueue<u32, 8> = pqueue!(4, 1, 3, 2, 5);
+
+        assert_eq!(pqueue.partition_point(|&elem| elem < 3), 2);
+        assert_eq!(pqueue.partition_point(|_| true), 5);
+        assert_eq!(pqueue.partition_point(|_| false), 0);
+
+        // Positions follow the comparator's order
+        let pqueue: PriorityQueue<u32, 8, _> = pqueue_by!(|a, b| b.cmp(a), 4, 1, 3, 2, 5);
+        assert_eq!(pqueue.partition_point(|&elem| elem > 3), 2);
+        assert_eq!(pqueue.binary_search(&2), Ok(3));
+    }
 }
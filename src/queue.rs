@@ -1,4 +1,8 @@
-use core::ops::{Index, IndexMut};
+use core::{
+    iter::{Product, Sum},
+    mem,
+    ops::{Index, IndexMut},
+};
 
 pub struct Queue<T, const N: usize> {
     arr: [Option<T>; N],
@@ -8,6 +12,8 @@ pub struct Queue<T, const N: usize> {
 
 impl<T, const N: usize> Queue<T, N> {
     pub const fn new() -> Self {
+        assert!(N > 0, "capacity N must be greater than 0");
+
         Self {
             arr: [const { None }; N],
             index: 0,
@@ -19,15 +25,43 @@ impl<T, const N: usize> Queue<T, N> {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Create a full queue where the element at each index `i` is `f(i)`, so `f(0)` is the first to
+    /// be popped
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        assert!(N > 0, "capacity N must be greater than 0");
+
+        Self {
+            arr: core::array::from_fn(|i| Some(f(i))),
+            index: 0,
+            len: N,
+        }
+    }
+
     /// Push a value to the back of the queue
     pub fn push_back(&mut self, elem: T) {
-        if self.len >= N {
+        if self.push_back_within_capacity(elem).is_err() {
             panic!("Attempt to add element to full queue");
         }
+    }
+
+    /// Push a value to the back of the queue like `push_back`, but return it in `Err` if the queue
+    /// is full instead of panicking. The queue is left unchanged in that case
+    pub fn push_back_within_capacity(&mut self, elem: T) -> Result<(), T> {
+        if self.len >= N {
+            return Err(elem);
+        }
 
         let pos = (self.index + self.len) % N;
         self.arr[pos] = Some(elem);
-        self.len += 1;
+        self.inc_len();
+        Ok(())
     }
 
     /// Pops a value from the front of the queue
@@ -38,27 +72,342 @@ impl<T, const N: usize> Queue<T, N> {
             let val = self.arr[self.index].take().unwrap_or_else(|| {
                 panic!("Unexpected None in backing array at index {}", self.index)
             });
-            self.len -= 1;
+            self.dec_len();
             self.index = (self.index + 1) % N;
 
             Some(val)
         }
     }
 
+    /// Remove the element at logical `index` and return it, replacing it with the back element.
+    /// This is O(1) but doesn't preserve the order of the queue. Panics if `index` is out of
+    /// bounds
+    pub fn swap_remove_back(&mut self, index: usize) -> T {
+        if index >= self.len {
+            panic!(
+                "Attempt to remove element at invalid index: {} where len is {}",
+                index, self.len
+            );
+        }
+
+        let pos = (self.index + index) % N;
+        let back = (self.index + self.len - 1) % N;
+        self.arr.swap(pos, back);
+
+        let val = self.arr[back]
+            .take()
+            .unwrap_or_else(|| panic!("Unexpected None in backing array at index {}", back));
+        self.dec_len();
+        val
+    }
+
+    /// Remove the element at logical `index` and return it, replacing it with the front element.
+    /// This is O(1) but doesn't preserve the order of the queue. Panics if `index` is out of
+    /// bounds
+    pub fn swap_remove_front(&mut self, index: usize) -> T {
+        if index >= self.len {
+            panic!(
+                "Attempt to remove element at invalid index: {} where len is {}",
+                index, self.len
+            );
+        }
+
+        let pos = (self.index + index) % N;
+        self.arr.swap(pos, self.index);
+
+        self.pop_front()
+            .unwrap_or_else(|| panic!("Unexpected empty queue with len {}", self.len))
+    }
+
+    /// Pop the front element only if `f` returns true for it, otherwise leave the queue unchanged
+    pub fn pop_front_if<F>(&mut self, f: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        if f(self.front()?) {
+            self.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// Remove all elements for which `f` returns false, keeping the FIFO order of the rest
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_count(f);
+    }
+
+    /// Remove all elements for which `f` returns false, keeping the FIFO order of the rest.
+    /// Returns the number of elements removed
+    pub fn retain_count<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut kept = 0;
+
+        for i in 0..self.len {
+            let pos = (self.index + i) % N;
+            let elem = self.arr[pos]
+                .take()
+                .unwrap_or_else(|| panic!("Unexpected None in backing array at index {}", pos));
+
+            if f(&elem) {
+                self.arr[(self.index + kept) % N] = Some(elem);
+                kept += 1;
+            }
+        }
+
+        let removed = self.len - kept;
+        self.len = kept;
+        removed
+    }
+
+    /// Clear the backing array entirely, destroying all elements. The next pushed element is
+    /// stored at the start of the backing array
+    pub fn clear(&mut self) {
+        self.clear_keep_position();
+        self.index = 0;
+    }
+
+    /// Clear the backing array entirely, destroying all elements, but keep the position of the
+    /// front of the queue. The next pushed element is stored where the front element used to be
+    pub fn clear_keep_position(&mut self) {
+        // The queue is emptied before any element is dropped, so it stays consistent even if a
+        // destructor panics
+        self.len = 0;
+        let old = mem::replace(&mut self.arr, [const { None }; N]);
+        drop(old);
+    }
+
+    /// Pop every element from the queue, passing each one to `f` from the front to the back
+    pub fn drain_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T),
+    {
+        while let Some(elem) = self.pop_front() {
+            f(elem);
+        }
+    }
+
+    /// Get a reference to the element `n` places from the front of the queue, or `None` if there
+    /// are not enough elements
+    pub fn peek_nth(&self, n: usize) -> Option<&T> {
+        if n >= self.len {
+            None
+        } else {
+            self.arr[(self.index + n) % N].as_ref()
+        }
+    }
+
+    /// Get a reference to the front element of the queue (the next to be popped)
+    pub fn front(&self) -> Option<&T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.arr[self.index].as_ref()
+        }
+    }
+
+    /// Get a mutable reference to the front element of the queue (the next to be popped)
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.arr[self.index].as_mut()
+        }
+    }
+
+    /// Get a reference to the back element of the queue (the most recently pushed)
+    pub fn back(&self) -> Option<&T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.arr[(self.index + self.len - 1) % N].as_ref()
+        }
+    }
+
+    /// Get a mutable reference to the back element of the queue (the most recently pushed)
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.arr[(self.index + self.len - 1) % N].as_mut()
+        }
+    }
+
+    /// Apply `f` to the front element of the queue, if there is one. Returns whether there was a
+    /// front element
+    pub fn modify_front<F>(&mut self, f: F) -> bool
+    where
+        F: FnOnce(&mut T),
+    {
+        self.front_mut().map(f).is_some()
+    }
+
+    /// Apply `f` to the back element of the queue, if there is one. Returns whether there was a
+    /// back element
+    pub fn modify_back<F>(&mut self, f: F) -> bool
+    where
+        F: FnOnce(&mut T),
+    {
+        self.back_mut().map(f).is_some()
+    }
+
+    /// Apply `f` to every element in place, from the front to the back
+    pub fn map_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        for i in 0..self.len {
+            f(&mut self[i]);
+        }
+    }
+
+    /// Call `f` on each element from the front to the back, stopping at the first error and
+    /// returning it
+    pub fn try_for_each<E, F>(&self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<(), E>,
+    {
+        self.iter().try_for_each(f)
+    }
+
+    /// Iterate over the elements from the front to the back, along with the index each one can be
+    /// accessed at
+    pub fn indexed(&self) -> impl ExactSizeIterator<Item = (usize, &T)> {
+        (0..self.len).map(|i| (i, &self[i]))
+    }
+
+    /// Sum all of the elements in the queue
+    pub fn sum(&self) -> T
+    where
+        T: Sum + Copy,
+    {
+        self.iter().copied().sum()
+    }
+
+    /// Multiply all of the elements in the queue
+    pub fn product(&self) -> T
+    where
+        T: Product + Copy,
+    {
+        self.iter().copied().product()
+    }
+
     pub fn iter(&self) -> QueueIter<'_, T, N> {
         QueueIter {
             base: self,
             index: 0,
         }
     }
+
+    /// Iterate over the queue from front to back like `iter`, also yielding the index in the
+    /// backing array that each element is stored at. The indices wrap around from `N - 1` to 0
+    pub fn iter_with_physical(&self) -> impl Iterator<Item = (usize, &T)> {
+        (0..self.len).map(|i| {
+            let pos = (self.index + i) % N;
+            let elem = self.arr[pos]
+                .as_ref()
+                .unwrap_or_else(|| panic!("Unexpected None at index {} of backing array", pos));
+            (pos, elem)
+        })
+    }
+
+    /// Find the logical index of the first element for which `pred` returns false, with the same
+    /// semantics as `slice::partition_point`. The queue must already be partitioned by `pred`
+    pub fn partition_point<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut low = 0;
+        let mut high = self.len;
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(&self[mid]) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        low
+    }
+
+    /// Iterate over the queue from front to back endlessly, starting again at the front after the
+    /// back. This is usually paired with `take`. If the queue is empty nothing is yielded
+    pub fn cycle(&self) -> impl Iterator<Item = &T> {
+        (0..self.len).cycle().map(|i| &self[i])
+    }
+
+    /// Move the elements into a queue with a capacity of `M`, returning `Err(self)` if they don't
+    /// fit. The new queue keeps the same FIFO order, but starts at the beginning of its backing
+    /// array rather than wrapping around it
+    pub fn to_capacity<const M: usize>(mut self) -> Result<Queue<T, M>, Self> {
+        if self.len > M {
+            return Err(self);
+        }
+
+        let mut queue = Queue::new();
+        while let Some(elem) = self.pop_front() {
+            queue.push_back(elem);
+        }
+
+        Ok(queue)
+    }
+
+    /// Increment the length, asserting in debug builds that it stays within the capacity
+    fn inc_len(&mut self) {
+        debug_assert!(
+            self.len < N,
+            "Attempt to increment len {} past capacity {}",
+            self.len,
+            N
+        );
+        self.len += 1;
+    }
+
+    /// Decrement the length, asserting in debug builds that it doesn't go below zero
+    fn dec_len(&mut self) {
+        debug_assert!(self.len > 0, "Attempt to decrement len below 0");
+        self.len -= 1;
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for Queue<T, N>
+where
+    T: Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        for elem in iter {
+            self.push_back(*elem);
+        }
+    }
 }
 
 impl<T, const N: usize> IndexMut<usize> for Queue<T, N> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        }
         let pos = (self.index + index) % N;
         self.arr[pos]
             .as_mut()
-            .unwrap_or_else(|| panic!("Invalid index access: {}", index))
+            .unwrap_or_else(|| panic!("Unexpected None at index {} of backing array", pos))
     }
 }
 
@@ -66,10 +415,25 @@ impl<T, const N: usize> Index<usize> for Queue<T, N> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.len {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            );
+        }
         let pos = (self.index + index) % N;
         self.arr[pos]
             .as_ref()
-            .unwrap_or_else(|| panic!("Invalid index access: {}", index))
+            .unwrap_or_else(|| panic!("Unexpected None at index {} of backing array", pos))
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Queue<T, N> {
+    type Item = &'a T;
+    type IntoIter = QueueIter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
@@ -90,6 +454,11 @@ impl<'a, T, const N: usize> Iterator for QueueIter<'a, T, N> {
             Some(elem)
         }
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n).min(self.base.len);
+        self.next()
+    }
 }
 
 #[macro_export]
@@ -104,6 +473,8 @@ macro_rules! queue {
 
 #[cfg(test)]
 mod tests {
+    use core::{cell::Cell, panic::AssertUnwindSafe};
+
     use super::*;
 
     #[test]
@@ -199,7 +570,7 @@ mod tests {
     #[test]
     fn test_pop_front() {
         let mut queue: Queue<u32, 10> = queue![1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
-        let mut exp_arr = queue.arr.clone();
+        let mut exp_arr = queue.arr;
         let mut exp_len = queue.len();
         let mut exp_index = queue.index;
 
@@ -318,4 +689,473 @@ mod tests {
             assert_eq!(i, *n as usize);
         }
     }
+
+    #[test]
+    fn test_front_back() {
+        let mut queue = Queue::<u32, 4>::new();
+        assert_eq!(queue.front(), None);
+        assert_eq!(queue.back(), None);
+        assert_eq!(queue.front_mut(), None);
+        assert_eq!(queue.back_mut(), None);
+
+        queue.push_back(1);
+        queue.push_back(2);
+        queue.push_back(3);
+        queue.pop_front();
+        queue.pop_front();
+        queue.push_back(4);
+        queue.push_back(5);
+
+        // The queue has now wrapped, with 5 at the start of the backing array
+        assert_eq!(queue.arr, [Some(5), None, Some(3), Some(4)]);
+        assert_eq!(queue.front(), Some(&3));
+        assert_eq!(queue.back(), Some(&5));
+
+        *queue.front_mut().unwrap() = 30;
+        *queue.back_mut().unwrap() = 50;
+
+        assert_eq!(queue.pop_front(), Some(30));
+        assert_eq!(queue.pop_front(), Some(4));
+        assert_eq!(queue.front(), Some(&50));
+        assert_eq!(queue.back(), Some(&50));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to increment len 2 past capacity 2")]
+    fn test_inc_len_past_capacity() {
+        let mut queue = Queue::<u32, 2>::new();
+        queue.push_back(1);
+        queue.push_back(2);
+
+        queue.inc_len();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to decrement len below 0")]
+    fn test_dec_len_below_zero() {
+        let mut queue = Queue::<u32, 2>::new();
+        queue.dec_len();
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let mut queue = Queue::<u32, 5>::from_fn(|i| i as u32 * i as u32);
+        assert_eq!(queue.len, 5);
+        assert_eq!(queue.arr, [0, 1, 4, 9, 16].map(Some));
+        assert_eq!(queue.pop_front(), Some(0));
+    }
+
+    #[test]
+    fn test_to_capacity() {
+        let mut queue: Queue<u32, 4> = queue![1, 2, 3, 4];
+        queue.pop_front();
+        queue.pop_front();
+        queue.push_back(5);
+
+        // The queue has wrapped, with 5 at the start of the backing array
+        assert_eq!(queue.arr, [Some(5), None, Some(3), Some(4)]);
+
+        let queue: Queue<u32, 8> = queue
+            .to_capacity()
+            .unwrap_or_else(|_| panic!("Expected queue to fit"));
+        assert_eq!(queue.index, 0);
+        assert_eq!(queue.len, 3);
+        assert_eq!(
+            queue.arr,
+            [Some(3), Some(4), Some(5), None, None, None, None, None]
+        );
+
+        let queue = queue.to_capacity::<2>().err().unwrap();
+        assert_eq!(queue.len, 3);
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let queue: Queue<u32, 10> = queue![1, 2, 3, 4];
+        assert_eq!(queue.sum(), 10);
+        assert_eq!(queue.product(), 24);
+
+        let empty = Queue::<u32, 10>::new();
+        assert_eq!(empty.sum(), 0);
+        assert_eq!(empty.product(), 1);
+    }
+
+    #[test]
+    fn test_retain_count() {
+        let mut queue: Queue<u32, 6> = queue![0, 0, 1, 2, 3, 4];
+        queue.pop_front();
+        queue.pop_front();
+        queue.push_back(5);
+        queue.push_back(6);
+
+        assert_eq!(queue.retain_count(|n| n % 2 == 1), 3);
+        assert_eq!(queue.len, 3);
+        assert_eq!(queue.arr, [None, None, Some(1), Some(3), Some(5), None]);
+
+        queue.retain(|n| *n > 1);
+        assert_eq!(queue.pop_front(), Some(3));
+        assert_eq!(queue.pop_front(), Some(5));
+        assert_eq!(queue.pop_front(), None);
+    }
+
+    #[test]
+    fn test_pop_front_if() {
+        let mut queue: Queue<u32, 10> = queue![1, 2, 3];
+
+        assert_eq!(queue.pop_front_if(|n| *n == 1), Some(1));
+        assert_eq!(queue.len, 2);
+
+        assert_eq!(queue.pop_front_if(|n| *n == 3), None);
+        assert_eq!(queue.len, 2);
+        assert_eq!(queue.front(), Some(&2));
+
+        let mut empty = Queue::<u32, 10>::new();
+        assert_eq!(empty.pop_front_if(|_| true), None);
+        assert_eq!(empty.len, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn test_index_oob_panic() {
+        let queue: Queue<u32, 10> = queue![1, 2, 3];
+        let _ = queue[3];
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut queue: Queue<u32, 5> = queue![1, 2, 3];
+        queue.pop_front();
+        queue.pop_front();
+
+        queue.clear();
+        assert_eq!(queue.len, 0);
+        assert_eq!(queue.index, 0);
+        assert_eq!(queue.arr, [None; 5]);
+
+        queue.push_back(4);
+        assert_eq!(queue.arr[0], Some(4));
+        assert_eq!(queue.front(), Some(&4));
+    }
+
+    #[test]
+    fn test_clear_keep_position() {
+        let mut queue: Queue<u32, 5> = queue![1, 2, 3];
+        queue.pop_front();
+        queue.pop_front();
+
+        queue.clear_keep_position();
+        assert_eq!(queue.len, 0);
+        assert_eq!(queue.index, 2);
+        assert_eq!(queue.arr, [None; 5]);
+
+        queue.push_back(4);
+        assert_eq!(queue.arr[2], Some(4));
+        assert_eq!(queue.front(), Some(&4));
+    }
+
+    #[test]
+    fn test_into_iter_ref() {
+        let mut queue: Queue<u32, 3> = queue![0, 1, 2];
+        queue.pop_front();
+        queue.push_back(3);
+
+        let mut expected = 1;
+        for elem in &queue {
+            assert_eq!(*elem, expected);
+            expected += 1;
+        }
+
+        assert_eq!(expected, 4);
+    }
+
+    #[test]
+    fn test_modify_front_back() {
+        let mut queue: Queue<u32, 3> = queue![1, 2, 3];
+        queue.pop_front();
+        queue.push_back(4);
+
+        assert!(queue.modify_front(|elem| *elem *= 10));
+        assert!(queue.modify_back(|elem| *elem += 1));
+        assert_eq!(queue.front(), Some(&20));
+        assert_eq!(queue.back(), Some(&5));
+        assert_eq!(queue.arr, [Some(5), Some(20), Some(3)]);
+
+        let mut empty: Queue<u32, 3> = Queue::new();
+        assert!(!empty.modify_front(|_| panic!("Called on empty queue")));
+        assert!(!empty.modify_back(|_| panic!("Called on empty queue")));
+    }
+
+    #[test]
+    fn test_extend_copied() {
+        let other: Queue<u32, 4> = queue![1, 2, 3];
+        let mut queue: Queue<u32, 8> = queue![0];
+
+        queue.extend(other.iter());
+
+        assert!(queue.iter().copied().eq([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_drain_with() {
+        let mut coll: Queue<u32, 5> = queue![1, 2, 3];
+        let mut drained = [0; 3];
+        let mut count = 0;
+
+        coll.drain_with(|elem| {
+            drained[count] = elem;
+            count += 1;
+        });
+
+        assert_eq!(count, 3);
+        assert_eq!(drained, [1, 2, 3]);
+        assert_eq!(coll.len, 0);
+        assert_eq!(coll.arr, [None; 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity N must be greater than 0")]
+    fn test_zero_capacity_panic() {
+        let mut queue = Queue::<u32, 0>::new();
+        queue.push_back(1);
+    }
+
+    #[test]
+    fn test_iter_with_physical() {
+        let mut queue: Queue<u32, 4> = queue![1, 2, 3, 4];
+        queue.pop_front();
+        queue.pop_front();
+        queue.push_back(5);
+
+        assert!(queue.iter_with_physical().eq([(2, &3), (3, &4), (0, &5)]));
+    }
+
+    #[test]
+    fn test_try_for_each() {
+        let coll: Queue<u32, 10> = queue![1, 2, 3, 4];
+        let mut visited = [0; 4];
+        let mut count = 0;
+
+        let result = coll.try_for_each(|elem| {
+            if *elem == 2 {
+                return Err(*elem * 10);
+            }
+            visited[count] = *elem;
+            count += 1;
+            Ok(())
+        });
+
+        assert_eq!(result, Err(20));
+        assert_eq!(visited[..count], [1]);
+        assert_eq!(coll.try_for_each(|_| Ok::<(), ()>(())), Ok(()));
+    }
+
+    #[test]
+    fn test_push_back_within_capacity() {
+        let mut queue: Queue<u32, 3> = queue![0, 1];
+        queue.pop_front();
+
+        assert_eq!(queue.push_back_within_capacity(2), Ok(()));
+        assert_eq!(queue.push_back_within_capacity(3), Ok(()));
+        assert_eq!(queue.push_back_within_capacity(4), Err(4));
+
+        assert_eq!(queue.index, 1);
+        assert_eq!(queue.len, 3);
+        assert_eq!(queue.arr, [Some(3), Some(1), Some(2)]);
+
+        assert_eq!(queue.pop_front(), Some(1));
+        assert_eq!(queue.pop_front(), Some(2));
+        assert_eq!(queue.pop_front(), Some(3));
+        assert_eq!(queue.pop_front(), None);
+    }
+
+    #[test]
+    fn test_peek_nth() {
+        let mut queue: Queue<u32, 4> = queue![0, 0, 1, 2];
+        queue.pop_front();
+        queue.pop_front();
+        queue.push_back(3);
+        queue.push_back(4);
+
+        assert_eq!(queue.peek_nth(0), Some(&1));
+        assert_eq!(queue.peek_nth(2), Some(&3));
+        assert_eq!(queue.peek_nth(3), Some(&4));
+        assert_eq!(queue.peek_nth(queue.len()), None);
+    }
+
+    #[test]
+    fn test_iter_nth() {
+        let mut queue: Queue<u32, 4> = queue![0, 0, 1, 2];
+        queue.pop_front();
+        queue.pop_front();
+        queue.push_back(3);
+        queue.push_back(4);
+
+        assert_eq!(queue.iter().nth(2), Some(&3));
+
+        let mut iter = queue.iter();
+        assert_eq!(iter.nth(1), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(queue.iter().nth(usize::MAX), None);
+    }
+
+    #[test]
+    fn test_indexed() {
+        let coll: Queue<u32, 10> = queue![10, 20, 30];
+
+        let indexed = coll.indexed();
+        assert_eq!(indexed.len(), 3);
+        assert!(indexed.eq([(0, &10), (1, &20), (2, &30)]));
+        assert_eq!(coll.indexed().nth(1), Some((1, &20)));
+    }
+
+    // An element that counts its drops, and panics when dropped if `panics` is set
+    struct DropBomb<'a> {
+        drops: &'a Cell<usize>,
+        panics: bool,
+    }
+    impl Drop for DropBomb<'_> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panics {
+                panic!("DropBomb exploded");
+            }
+        }
+    }
+
+    #[test]
+    fn test_clear_panicking_drop() {
+        extern crate std;
+
+        let drops = Cell::new(0);
+        let mut queue: Queue<DropBomb, 5> = Queue::new();
+        queue.push_back(DropBomb {
+            drops: &drops,
+            panics: false,
+        });
+        queue.push_back(DropBomb {
+            drops: &drops,
+            panics: true,
+        });
+        queue.push_back(DropBomb {
+            drops: &drops,
+            panics: false,
+        });
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| queue.clear()));
+
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 3);
+        assert_eq!(queue.len, 0);
+        assert!(queue.arr.iter().all(Option::is_none));
+
+        queue.push_back(DropBomb {
+            drops: &drops,
+            panics: false,
+        });
+        assert_eq!(queue.len, 1);
+        drop(queue);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let queue: Queue<u32, 5> = queue![1, 2, 3];
+        assert!(queue.cycle().take(7).eq([1, 2, 3, 1, 2, 3, 1].iter()));
+
+        let empty: Queue<u32, 5> = Queue::new();
+        assert_eq!(empty.cycle().next(), None);
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let mut queue: Queue<u32, 5> = queue![0, 0, 1, 2, 3];
+        queue.pop_front();
+        queue.pop_front();
+        queue.push_back(4);
+        queue.push_back(5);
+
+        for k in 0..7 {
+            assert_eq!(
+                queue.partition_point(|x| *x < k),
+                k.saturating_sub(1) as usize
+            );
+        }
+        assert_eq!(Queue::<u32, 5>::new().partition_point(|_| true), 0);
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        // Start partway through the backing array so the elements wrap around
+        let mut queue: Queue<u32, 4> = Queue::new();
+        queue.push_back(0);
+        queue.push_back(0);
+        queue.pop_front();
+        queue.pop_front();
+        for elem in [1, 20, 3, 40] {
+            queue.push_back(elem);
+        }
+
+        queue.map_in_place(|elem| *elem = (*elem).min(10));
+        assert!(queue.iter().copied().eq([1, 10, 3, 10]));
+        assert_eq!(queue.arr, [Some(3), Some(10), Some(1), Some(10)]);
+    }
+
+    #[test]
+    fn test_swap_remove_back() {
+        // Start partway through the backing array so the elements wrap around
+        let mut queue: Queue<u32, 5> = Queue::new();
+        for elem in [0, 0, 0] {
+            queue.push_back(elem);
+        }
+        for _ in 0..3 {
+            queue.pop_front();
+        }
+        for elem in [1, 2, 3, 4, 5] {
+            queue.push_back(elem);
+        }
+
+        assert_eq!(queue.swap_remove_back(1), 2);
+        assert!(queue.iter().copied().eq([1, 5, 3, 4]));
+
+        // Removing the back element itself just pops it
+        assert_eq!(queue.swap_remove_back(3), 4);
+        assert!(queue.iter().copied().eq([1, 5, 3]));
+
+        assert_eq!(queue.swap_remove_back(0), 1);
+        assert!(queue.iter().copied().eq([3, 5]));
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn test_swap_remove_front() {
+        let mut queue: Queue<u32, 5> = Queue::new();
+        for elem in [1, 2, 3, 4] {
+            queue.push_back(elem);
+        }
+
+        assert_eq!(queue.swap_remove_front(2), 3);
+        assert!(queue.iter().copied().eq([2, 1, 4]));
+
+        assert_eq!(queue.swap_remove_front(0), 2);
+        assert!(queue.iter().copied().eq([1, 4]));
+
+        queue.push_back(6);
+        queue.push_back(7);
+        queue.push_back(8);
+        assert_eq!(queue.swap_remove_front(4), 8);
+        assert!(queue.iter().copied().eq([4, 6, 7, 1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to remove element at invalid index: 2 where len is 2")]
+    fn test_swap_remove_back_oob_panic() {
+        let mut queue: Queue<u32, 5> = Queue::new();
+        queue.push_back(1);
+        queue.push_back(2);
+        queue.swap_remove_back(2);
+    }
 }
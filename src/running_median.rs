@@ -0,0 +1,102 @@
+use crate::SearchableList;
+
+/// Tracks the median of the last `N` values pushed. Older values are evicted in FIFO order once
+/// it is full, and the median is available in O(1)
+pub struct RunningMedian<T, const N: usize>
+where
+    T: Ord,
+{
+    values: SearchableList<T, N>,
+}
+
+impl<T, const N: usize> RunningMedian<T, N>
+where
+    T: Ord,
+{
+    pub const fn new() -> Self {
+        Self {
+            values: SearchableList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Add a value, first evicting the oldest value if there are already `N`. Returns the evicted
+    /// value, if any
+    pub fn push(&mut self, value: T) -> Option<T> {
+        let evicted = if self.values.len() >= N {
+            self.values.pop_front()
+        } else {
+            None
+        };
+
+        self.values.push(value);
+        evicted
+    }
+
+    /// Get the median of the current values. When there is an even number of values, this is the
+    /// lower of the two middle values
+    pub fn median(&self) -> Option<&T> {
+        if self.values.is_empty() {
+            None
+        } else {
+            self.values.get_sorted((self.values.len() - 1) / 2)
+        }
+    }
+}
+
+impl<T, const N: usize> Default for RunningMedian<T, N>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median() {
+        let mut median = RunningMedian::<u32, 10>::new();
+        assert_eq!(median.median(), None);
+
+        let expected = [5, 1, 5, 3, 5, 5];
+        for (value, exp) in [5, 1, 9, 3, 7, 6].into_iter().zip(expected) {
+            assert_eq!(median.push(value), None);
+            assert_eq!(median.median(), Some(&exp));
+        }
+        assert_eq!(median.len(), 6);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let mut median = RunningMedian::<u32, 3>::new();
+
+        assert_eq!(median.push(1), None);
+        assert_eq!(median.push(2), None);
+        assert_eq!(median.push(3), None);
+        assert_eq!(median.median(), Some(&2));
+
+        // Window is [2, 3, 10]
+        assert_eq!(median.push(10), Some(1));
+        assert_eq!(median.median(), Some(&3));
+
+        // Window is [3, 10, 0]
+        assert_eq!(median.push(0), Some(2));
+        assert_eq!(median.median(), Some(&3));
+
+        // Window is [10, 0, 0]
+        assert_eq!(median.push(0), Some(3));
+        assert_eq!(median.median(), Some(&0));
+        assert_eq!(median.len(), 3);
+    }
+}
@@ -1,8 +1,9 @@
-use core::{cmp::Ordering, ops::Index};
+use core::{cmp::Ordering, mem, ops::Index};
 
-/// This module provides a list type that can be searched and indexed efficiently (O(1)). It
-/// potentially involves restructuring the backing array when a
+use crate::List;
 
+/// This module provides a list type that can be searched and indexed efficiently (O(1)). It
+/// potentially involves restructuring the backing array when an element is added or removed
 pub struct SearchableList<T, const N: usize>
 where
     T: Ord,
@@ -21,6 +22,8 @@ where
 {
     pub const N: usize = N;
 
+    /// Create an empty list. A capacity `N` of 0 is allowed and gives a list that is always full,
+    /// so every push panics and every pop returns `None`
     pub const fn new() -> Self {
         Self {
             backing: [const { None }; N],
@@ -39,6 +42,10 @@ where
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     /// Push an element to the **back** of the list
     pub fn push(&mut self, elem: T) {
         if self.len >= N {
@@ -68,7 +75,39 @@ where
 
         self.backing[spot] = Some((self.len, elem));
         self.indices[self.len] = Some(spot);
-        self.len += 1;
+        self.inc_len();
+    }
+
+    /// Push an element to the **front** of the list, so it becomes index 0 in insertion order.
+    /// Every existing element moves up one place in insertion order
+    pub fn push_front(&mut self, elem: T) {
+        if self.len >= N {
+            panic!("Tried to add element to full list")
+        }
+
+        // Go before any equal elements, which keeps them in insertion order in the backing array
+        let (spot, _) = self.equal_range(&elem);
+        for j in (spot..self.len).rev() {
+            self.backing[j + 1] = self.backing[j].take();
+        }
+        for i in (0..self.len).rev() {
+            self.indices[i + 1] = self.indices[i].take();
+        }
+
+        // Everything after the new element in sorted order moved up one spot, and every element
+        // moved up one place in insertion order
+        for index_entry in self.indices[1..=self.len].iter_mut().flatten() {
+            if *index_entry >= spot {
+                *index_entry += 1;
+            }
+        }
+        for (i, _) in self.backing[..=self.len].iter_mut().flatten() {
+            *i += 1;
+        }
+
+        self.backing[spot] = Some((0, elem));
+        self.indices[0] = Some(spot);
+        self.inc_len();
     }
 
     /// Pop an element from the **back** of the list
@@ -88,12 +127,12 @@ where
                         if let Some((i2, elem)) = self.backing[j2 + 1].take()
                             && let Some(index_entry) = self.indices[i2].as_mut()
                         {
-                            *index_entry = *index_entry - 1;
+                            *index_entry -= 1;
                             self.backing[j2] = Some((i2, elem));
                         }
                     }
 
-                    self.len -= 1;
+                    self.dec_len();
                     self.indices[self.len] = None;
                     self.backing[self.len] = None;
 
@@ -114,10 +153,174 @@ where
         }
     }
 
+    /// Pop an element from the **front** of the list (the oldest element). Every remaining element
+    /// moves down one place in insertion order
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let j = self.indices[0].unwrap_or_else(|| {
+            panic!(
+                "Unexpected None at index 0 of SList indices arr, with len = {}",
+                self.len
+            )
+        });
+        let (_, elem) = self.backing[j].take().unwrap_or_else(|| {
+            panic!(
+                "Unexpected None at index {} of SList backing arr, with len = {}",
+                j, self.len
+            )
+        });
+
+        // Close the gaps left in both arrays
+        for j2 in j..self.len - 1 {
+            self.backing[j2] = self.backing[j2 + 1].take();
+        }
+        for i in 0..self.len - 1 {
+            self.indices[i] = self.indices[i + 1].take();
+        }
+        self.indices[self.len - 1] = None;
+        self.dec_len();
+
+        // Everything after the removed element in sorted order moved down one spot, and every
+        // element moved down one place in insertion order
+        for index_entry in self.indices[..self.len].iter_mut().flatten() {
+            if *index_entry > j {
+                *index_entry -= 1;
+            }
+        }
+        for (i, _) in self.backing[..self.len].iter_mut().flatten() {
+            *i -= 1;
+        }
+
+        Some(elem)
+    }
+
+    /// Remove all elements from the list, resetting both backing arrays
+    pub fn clear(&mut self) {
+        // The list is emptied before any element is dropped, so it stays consistent even if a
+        // destructor panics
+        self.len = 0;
+        self.indices = [const { None }; N];
+        let old = mem::replace(&mut self.backing, [const { None }; N]);
+        drop(old);
+    }
+
+    /// Get the element at position `sorted_index` in sorted (ascending) order. Indexing the list
+    /// directly uses insertion order instead
+    pub fn get_sorted(&self, sorted_index: usize) -> Option<&T> {
+        if sorted_index >= self.len {
+            None
+        } else {
+            self.backing[sorted_index].as_ref().map(|(_, elem)| elem)
+        }
+    }
+
+    /// Iterate over the elements in sorted (ascending) order, rather than insertion order
+    pub fn iter_sorted(&self) -> SortedIter<'_, T, N> {
+        SortedIter {
+            base: self,
+            front: 0,
+            back: self.len,
+        }
+    }
+
     pub fn find(&self, elem: &T) -> Option<usize> {
         self.search_for_existing_spot_by(|el| el.cmp(elem), 0, self.len)
     }
 
+    /// Iterate over the insertion-order indices of every element equal to `elem`. Indices are
+    /// yielded in sorted order of the matching elements, which for equal elements is unspecified
+    pub fn find_all(&self, elem: &T) -> impl Iterator<Item = usize> + '_ {
+        let (start, end) = self.equal_range(elem);
+
+        self.backing[start..end]
+            .iter()
+            .filter_map(|entry| entry.as_ref().map(|(i, _)| *i))
+    }
+
+    /// Get the range `[lo, hi)` of sorted positions holding elements equal to `elem`, in the same
+    /// order as `get_sorted`. If there are none, both bounds are the position `elem` would be
+    /// inserted at
+    pub fn equal_range(&self, elem: &T) -> (usize, usize) {
+        let live = &self.backing[..self.len];
+        let key = |entry: &Option<(usize, T)>| match entry {
+            Some((_, el)) => el.cmp(elem),
+            None => panic!(
+                "Unexpected None in SList backing arr, with len = {}",
+                self.len
+            ),
+        };
+
+        let lo = live.partition_point(|entry| key(entry) == Ordering::Less);
+        let hi = live.partition_point(|entry| key(entry) != Ordering::Greater);
+
+        (lo, hi)
+    }
+
+    /// Move the elements into a `List` in sorted (ascending) order
+    pub fn into_sorted_list(self) -> List<T, N> {
+        let mut list = List::new();
+        for (_, elem) in self.backing.into_iter().flatten() {
+            list.push_back(elem);
+        }
+
+        list
+    }
+
+    /// Move the elements into a `List` in insertion order, so the list can be indexed the same way
+    pub fn into_insertion_order_list(mut self) -> List<T, N> {
+        let mut list = List::new();
+        for i in 0..self.len {
+            let (_, elem) = self.indices[i]
+                .and_then(|j| self.backing[j].take())
+                .unwrap_or_else(|| panic!("Missing element {} of SList with len {}", i, self.len));
+            list.push_back(elem);
+        }
+
+        list
+    }
+
+    /// Push every element of `iter` until the list is full. If the list fills up, the rest of the
+    /// iterator is returned in `Err` without having been advanced any further, so none of its
+    /// elements are lost. This happens whenever the list ends up full, even if the iterator has no
+    /// elements left
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), I::IntoIter>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+
+        loop {
+            if self.len >= N {
+                return Err(iter);
+            }
+
+            match iter.next() {
+                Some(elem) => self.push(elem),
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Increment the length, asserting in debug builds that it stays within the capacity
+    fn inc_len(&mut self) {
+        debug_assert!(
+            self.len < N,
+            "Attempt to increment len {} past capacity {}",
+            self.len,
+            N
+        );
+        self.len += 1;
+    }
+
+    /// Decrement the length, asserting in debug builds that it doesn't go below zero
+    fn dec_len(&mut self) {
+        debug_assert!(self.len > 0, "Attempt to decrement len below 0");
+        self.len -= 1;
+    }
+
     fn search_for_existing_spot_by<F>(&self, f: F, start_j: usize, end_j: usize) -> Option<usize>
     where
         F: Fn(&T) -> Ordering,
@@ -231,13 +434,13 @@ where
                 (Some((i, _)), Some(_)) => {
                     let mj2 = self.indices[*i];
 
-                    if let Some(j2) = mj2 {
-                        if j2 != index {
-                            panic!(
-                                "Mismatched indices: backing array at index {} contains {} but indices[{}] contains {}",
-                                index, i, i, j2
-                            )
-                        }
+                    if let Some(j2) = mj2
+                        && j2 != index
+                    {
+                        panic!(
+                            "Mismatched indices: backing array at index {} contains {} but indices[{}] contains {}",
+                            index, i, i, j2
+                        )
                     }
                 }
                 (None, Some(_)) => panic!(
@@ -267,6 +470,29 @@ where
     }
 }
 
+impl<T, const N: usize> Default for SearchableList<T, N>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SearchableList<T, N>
+where
+    T: Ord,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
 impl<T, const N: usize> Index<usize> for SearchableList<T, N>
 where
     T: Ord,
@@ -276,8 +502,8 @@ where
     fn index(&self, index: usize) -> &Self::Output {
         if index >= self.len {
             panic!(
-                "Attempted to access index {} of SList with len {}",
-                index, self.len
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
             )
         } else if let Some(j) = self.indices[index]
             && let Some((_, elem)) = &self.backing[j]
@@ -289,6 +515,66 @@ where
     }
 }
 
+pub struct SortedIter<'a, T, const N: usize>
+where
+    T: Ord,
+{
+    base: &'a SearchableList<T, N>,
+    // front and back are cursors into the backing array, the remaining elements are
+    // backing[front..back]
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T, const N: usize> SortedIter<'a, T, N>
+where
+    T: Ord,
+{
+    fn elem_at(&self, j: usize) -> &'a T {
+        let base = self.base;
+        &base.backing[j]
+            .as_ref()
+            .unwrap_or_else(|| {
+                panic!(
+                    "Unexpected None at index {} of backing array for searchable list with len {}",
+                    j, base.len
+                )
+            })
+            .1
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for SortedIter<'a, T, N>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            None
+        } else {
+            let elem = self.elem_at(self.front);
+            self.front += 1;
+            Some(elem)
+        }
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for SortedIter<'a, T, N>
+where
+    T: Ord,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            None
+        } else {
+            self.back -= 1;
+            Some(self.elem_at(self.back))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -454,7 +740,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Attempted to access index 4 of SList with len 4")]
+    #[should_panic(expected = "index out of bounds: the len is 4 but the index is 4")]
     fn test_index_oob_panic() {
         let mut slist = SearchableList::<u32, 10>::new();
         slist.push(1);
@@ -467,7 +753,7 @@ mod tests {
         assert_eq!(slist[2], 2);
         assert_eq!(slist[3], 0);
 
-        slist[4];
+        let _ = slist[4];
     }
 
     #[test]
@@ -483,4 +769,297 @@ mod tests {
         assert_eq!(slist.find(&2), Some(2));
         assert_eq!(slist.find(&3), Some(1));
     }
+
+    #[test]
+    fn test_iter_sorted() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.push(3);
+        slist.push(1);
+        slist.push(2);
+
+        let mut iter = slist.iter_sorted();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = slist.iter_sorted().rev();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_iter_sorted_mixed_ends() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.push(4);
+        slist.push(3);
+        slist.push(1);
+        slist.push(2);
+
+        let mut iter = slist.iter_sorted();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to increment len 2 past capacity 2")]
+    fn test_inc_len_past_capacity() {
+        let mut slist = SearchableList::<u32, 2>::new();
+        slist.push(1);
+        slist.push(2);
+
+        slist.inc_len();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to decrement len below 0")]
+    fn test_dec_len_below_zero() {
+        let mut slist = SearchableList::<u32, 2>::new();
+        slist.dec_len();
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.push(1);
+        slist.push(3);
+        slist.push(2);
+
+        slist.clear();
+
+        assert_eq!(slist.len(), 0);
+        assert_eq!(slist.backing, [None; 10]);
+        assert_eq!(slist.indices, [None; 10]);
+        assert_eq!(slist.find(&1), None);
+        slist.verify_invariates();
+
+        slist.push(5);
+        slist.push(4);
+
+        let mut exp_backing = [None; 10];
+        let mut exp_indices = [None; 10];
+        exp_backing[0] = Some((1, 4));
+        exp_backing[1] = Some((0, 5));
+        exp_indices[0] = Some(1);
+        exp_indices[1] = Some(0);
+
+        assert_eq!(slist.len(), 2);
+        assert_eq!(slist.backing, exp_backing);
+        assert_eq!(slist.indices, exp_indices);
+        assert_eq!(slist.find(&4), Some(1));
+        slist.verify_invariates();
+    }
+
+    #[test]
+    fn test_get_sorted() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.push(3);
+        slist.push(1);
+        slist.push(2);
+
+        assert_eq!(slist.get_sorted(0), Some(&1));
+        assert_eq!(slist.get_sorted(1), Some(&2));
+        assert_eq!(slist.get_sorted(2), Some(&3));
+        assert_eq!(slist.get_sorted(3), None);
+
+        assert_eq!(slist[0], 3);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.push(3);
+        slist.push(1);
+        slist.push(3);
+        slist.push(2);
+        slist.push(3);
+
+        let mut found = [0; 3];
+        let mut count = 0;
+        for i in slist.find_all(&3) {
+            found[count] = i;
+            count += 1;
+        }
+        found.sort();
+
+        assert_eq!(count, 3);
+        assert_eq!(found, [0, 2, 4]);
+        assert!(slist.find_all(&1).eq([1]));
+        assert_eq!(slist.find_all(&5).count(), 0);
+        slist.verify_invariates();
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.push(5);
+        slist.extend([3, 1, 2]);
+
+        assert_eq!(slist.len, 4);
+        assert_eq!(slist.find(&5), Some(0));
+        assert_eq!(slist.find(&3), Some(1));
+        assert_eq!(slist.find(&1), Some(2));
+        assert_eq!(slist.find(&2), Some(3));
+        slist.verify_invariates();
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to add element to full list")]
+    fn test_extend_full_panic() {
+        let mut slist = SearchableList::<u32, 2>::new();
+        slist.extend([3, 1, 2]);
+    }
+
+    #[test]
+    fn test_try_extend() {
+        let mut slist = SearchableList::<u32, 3>::new();
+        assert!(slist.try_extend([2, 1]).is_ok());
+
+        let mut rest = slist.try_extend([4, 3, 0]).unwrap_err();
+        assert_eq!(rest.next(), Some(3));
+        assert_eq!(rest.next(), Some(0));
+
+        assert_eq!(slist.len, 3);
+        assert_eq!(slist.find(&4), Some(2));
+        slist.verify_invariates();
+    }
+
+    #[test]
+    fn test_equal_range() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.extend([4, 2, 6, 4, 0, 4]);
+
+        assert_eq!(slist.equal_range(&4), (2, 5));
+        for j in 2..5 {
+            assert_eq!(slist.get_sorted(j), Some(&4));
+        }
+
+        assert_eq!(slist.equal_range(&3), (2, 2));
+        assert_eq!(slist.equal_range(&7), (6, 6));
+        assert_eq!(slist.equal_range(&0), (0, 1));
+    }
+
+    #[test]
+    fn test_pop_front() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.extend([1, 3, 2, 0, 2]);
+
+        assert_eq!(slist.pop_front(), Some(1));
+        slist.verify_invariates();
+        assert_eq!(slist.len, 4);
+        assert_eq!(slist[0], 3);
+        assert_eq!(slist[1], 2);
+        assert_eq!(slist[2], 0);
+        assert_eq!(slist[3], 2);
+        assert!(slist.iter_sorted().eq([0, 2, 2, 3].iter()));
+
+        assert_eq!(slist.pop_front(), Some(3));
+        slist.verify_invariates();
+        assert_eq!(slist.find(&0), Some(1));
+
+        assert_eq!(slist.pop_front(), Some(2));
+        assert_eq!(slist.pop_front(), Some(0));
+        assert_eq!(slist.pop_front(), Some(2));
+        assert_eq!(slist.pop_front(), None);
+        assert_eq!(slist.backing, [None; 10]);
+        assert_eq!(slist.indices, [None; 10]);
+    }
+
+    #[test]
+    fn test_into_list() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.extend([3, 1, 2]);
+        assert!(slist.into_sorted_list() == [1, 2, 3]);
+
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.extend([3, 1, 2]);
+        assert!(slist.into_insertion_order_list() == [3, 1, 2]);
+    }
+
+    #[test]
+    fn test_push_front() {
+        let mut slist = SearchableList::<u32, 10>::new();
+        slist.extend([2, 3]);
+
+        slist.push_front(1);
+        slist.verify_invariates();
+        assert_eq!(slist.len(), 3);
+        assert_eq!(slist[0], 1);
+        assert_eq!(slist[1], 2);
+        assert_eq!(slist[2], 3);
+        assert!(slist.iter_sorted().eq([1, 2, 3].iter()));
+
+        slist.push_front(5);
+        slist.push_front(3);
+        slist.verify_invariates();
+        assert_eq!(slist[0], 3);
+        assert_eq!(slist[1], 5);
+        assert_eq!(slist.find(&5), Some(1));
+        assert!(slist.iter_sorted().eq([1, 2, 3, 3, 5].iter()));
+
+        // Equal elements stay in insertion order in the backing array
+        assert!(slist.find_all(&3).eq([0, 4]));
+
+        assert_eq!(slist.pop(), Some(3));
+        assert_eq!(slist.pop_front(), Some(3));
+        slist.verify_invariates();
+        assert!(slist.into_insertion_order_list() == [5, 1, 2]);
+    }
+
+    #[test]
+    fn test_push_front_empty_and_full() {
+        let mut slist = SearchableList::<u32, 3>::new();
+        slist.push_front(7);
+        slist.verify_invariates();
+        assert_eq!(slist[0], 7);
+
+        slist.push_front(9);
+        slist.push_front(8);
+        slist.verify_invariates();
+        assert!(slist.into_insertion_order_list() == [8, 9, 7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to add element to full list")]
+    fn test_push_front_full_panic() {
+        let mut slist = SearchableList::<u32, 1>::new();
+        slist.push_front(1);
+        slist.push_front(2);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut slist = SearchableList::<u32, 0>::new();
+
+        assert!(slist.is_empty());
+        assert_eq!(slist.pop(), None);
+        assert_eq!(slist.pop_front(), None);
+        assert_eq!(slist.find(&1), None);
+        assert_eq!(slist.equal_range(&1), (0, 0));
+        assert_eq!(slist.iter_sorted().count(), 0);
+        assert!(slist.try_extend([1]).is_err());
+        slist.verify_invariates();
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to add element to full list")]
+    fn test_zero_capacity_push_panic() {
+        let mut slist = SearchableList::<u32, 0>::new();
+        slist.push(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tried to add element to full list")]
+    fn test_zero_capacity_push_front_panic() {
+        let mut slist = SearchableList::<u32, 0>::new();
+        slist.push_front(1);
+    }
 }
@@ -1,4 +1,11 @@
-use core::ops::{Index, IndexMut};
+use core::{
+    iter::{Peekable, Product, Rev, Sum},
+    mem,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use crate::CapacityError;
 
 pub struct Stack<T, const N: usize> {
     arr: [Option<T>; N],
@@ -6,6 +13,8 @@ pub struct Stack<T, const N: usize> {
 }
 
 impl<T, const N: usize> Stack<T, N> {
+    /// Create an empty stack. A capacity `N` of 0 is allowed and gives a stack that is always full,
+    /// so every push panics and every pop returns `None`
     pub const fn new() -> Self {
         Self {
             arr: [const { None }; N],
@@ -17,6 +26,22 @@ impl<T, const N: usize> Stack<T, N> {
         self.len
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Create a full stack where the element at each position `i` of the backing array is `f(i)`,
+    /// so `f(0)` is at the bottom of the stack and `f(N - 1)` is at the top
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        Self {
+            arr: core::array::from_fn(|i| Some(f(i))),
+            len: N,
+        }
+    }
+
     /// Push a value to the front of the stack (the back of the backing array)
     pub fn push(&mut self, elem: T) {
         if self.len >= N {
@@ -24,7 +49,32 @@ impl<T, const N: usize> Stack<T, N> {
         }
 
         self.arr[self.len] = Some(elem);
-        self.len += 1;
+        self.inc_len();
+    }
+
+    /// Panic if there isn't room to push `n` more elements, so that an overflow is reported before
+    /// a batch of pushes starts rather than partway through it
+    pub fn assert_can_push(&self, n: usize) {
+        let free = N - self.len;
+        if n > free {
+            panic!("cannot push {} elements: only {} slots free", n, free);
+        }
+    }
+
+    /// Push every element of `iter` until the stack is full. Returns `None` if every element fit.
+    /// Otherwise the rest of the iterator is returned, starting with the first element that didn't
+    /// fit, so none of its elements are lost
+    pub fn push_all<I>(&mut self, iter: I) -> Option<Peekable<I::IntoIter>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter().peekable();
+
+        while let Some(elem) = iter.next_if(|_| self.len < N) {
+            self.push(elem);
+        }
+
+        iter.peek().is_some().then_some(iter)
     }
 
     /// Pop a value from the front of the stack (the back of the backing array)
@@ -32,23 +82,225 @@ impl<T, const N: usize> Stack<T, N> {
         if self.len == 0 {
             None
         } else {
-            self.len -= 1;
+            self.dec_len();
             self.arr[self.len].take()
         }
     }
 
+    /// Pop the top value of the stack only if `f` returns true for it, otherwise leave the stack
+    /// unchanged
+    pub fn pop_if<F>(&mut self, f: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        if self.len == 0 || !f(&self[0]) {
+            None
+        } else {
+            self.pop()
+        }
+    }
+
     /// Clear the backing array entirely, destroying all elements
     pub fn clear(&mut self) {
-        self.arr = [const { None }; N];
+        // The stack is emptied before any element is dropped, so it stays consistent even if a
+        // destructor panics
         self.len = 0;
+        let old = mem::replace(&mut self.arr, [const { None }; N]);
+        drop(old);
+    }
+
+    /// Keep only the bottom `n` elements (the first `n` pushed), dropping the rest from the top.
+    /// Does nothing if the stack has `n` or fewer elements
+    pub fn keep_prefix(&mut self, n: usize) {
+        while self.len > n {
+            self.pop();
+        }
+    }
+
+    /// Keep only the top `n` elements (the last `n` pushed), dropping the rest from the bottom.
+    /// The kept elements are shifted down to the bottom of the backing array. Does nothing if the
+    /// stack has `n` or fewer elements
+    pub fn keep_suffix(&mut self, n: usize) {
+        if n < self.len {
+            // Move the elements to drop to the top, where they can be popped off
+            self.arr[..self.len].rotate_left(self.len - n);
+            self.keep_prefix(n);
+        }
+    }
+
+    /// Pop every element from the stack, passing each one to `f` from the top down
+    pub fn drain_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T),
+    {
+        while let Some(elem) = self.pop() {
+            f(elem);
+        }
+    }
+
+    /// Check whether `elem` is within the top `depth` elements of the stack, searching from the top
+    /// down and stopping at the first match
+    pub fn contains_within(&self, depth: usize, elem: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().take(depth).any(|e| e == elem)
+    }
+
+    /// Call `f` on each element from the top down, stopping at the first error and returning it
+    pub fn try_for_each<E, F>(&self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<(), E>,
+    {
+        self.iter().try_for_each(f)
+    }
+
+    /// Iterate over the elements from the top down, so index 0 is the top, along with the index
+    /// each one can be accessed at
+    pub fn indexed(&self) -> impl ExactSizeIterator<Item = (usize, &T)> {
+        (0..self.len).map(|i| (i, &self[i]))
+    }
+
+    /// Sum all of the elements in the stack
+    pub fn sum(&self) -> T
+    where
+        T: Sum + Copy,
+    {
+        self.iter().copied().sum()
+    }
+
+    /// Multiply all of the elements in the stack
+    pub fn product(&self) -> T
+    where
+        T: Product + Copy,
+    {
+        self.iter().copied().product()
     }
 
     pub fn iter(&self) -> StackIter<'_, T, N> {
         StackIter {
-            base: &self,
+            base: self,
             index: 0,
         }
     }
+
+    /// Iterate over the stack from the bottom to the top, which is the order of the backing array.
+    /// This is the reverse of `iter`, which starts at the top
+    pub fn iter_bottom_up(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.arr[..self.len].iter().map(|elem| {
+            elem.as_ref()
+                .unwrap_or_else(|| panic!("Unexpected None in backing array of Stack"))
+        })
+    }
+
+    /// Call `f` on each element from the top of the stack to the bottom, the same order as `iter`
+    pub fn for_each_top_down<F>(&self, f: F)
+    where
+        F: FnMut(&T),
+    {
+        self.iter_bottom_up().rev().for_each(f);
+    }
+
+    /// Iterate mutably over the stack, from the top to the bottom (the same order as `iter`)
+    pub fn iter_mut(&mut self) -> StackIterMut<'_, T> {
+        StackIterMut {
+            inner: self.arr[..self.len].iter_mut().rev(),
+        }
+    }
+
+    /// Apply `f` to every element in place, from the top down
+    pub fn map_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        for i in 0..self.len {
+            f(&mut self[i]);
+        }
+    }
+
+    /// Move the elements into a stack with a capacity of `M`, returning `Err(self)` if they don't
+    /// fit. The order of elements from bottom to top is preserved
+    pub fn to_capacity<const M: usize>(mut self) -> Result<Stack<T, M>, Self> {
+        if self.len > M {
+            return Err(self);
+        }
+
+        let mut stack = Stack::new();
+        for i in 0..self.len {
+            stack.arr[i] = self.arr[i].take();
+        }
+        stack.len = self.len;
+
+        Ok(stack)
+    }
+
+    /// Increment the length, asserting in debug builds that it stays within the capacity
+    fn inc_len(&mut self) {
+        debug_assert!(
+            self.len < N,
+            "Attempt to increment len {} past capacity {}",
+            self.len,
+            N
+        );
+        self.len += 1;
+    }
+
+    /// Decrement the length, asserting in debug builds that it doesn't go below zero
+    fn dec_len(&mut self) {
+        debug_assert!(self.len > 0, "Attempt to decrement len below 0");
+        self.len -= 1;
+    }
+}
+
+impl<T, const N: usize> TryFrom<&[T]> for Stack<T, N>
+where
+    T: Clone,
+{
+    type Error = CapacityError;
+
+    fn try_from(value: &[T]) -> Result<Self, Self::Error> {
+        if value.len() > N {
+            return Err(CapacityError {
+                capacity: N,
+                required: value.len(),
+            });
+        }
+
+        let mut stack = Self::new();
+        for elem in value {
+            stack.push(elem.clone());
+        }
+
+        Ok(stack)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for Stack<u8, N> {
+    type Error = CapacityError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_bytes())
+    }
+}
+
+impl<T, const N: usize> Default for Stack<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for Stack<T, N>
+where
+    T: Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        for elem in iter {
+            self.push(*elem);
+        }
+    }
 }
 
 impl<T, const N: usize> Index<usize> for Stack<T, N> {
@@ -57,8 +309,8 @@ impl<T, const N: usize> Index<usize> for Stack<T, N> {
     fn index(&self, index: usize) -> &Self::Output {
         if index >= self.len {
             panic!(
-                "Attempt to access invalid index {} on Stack with length {}",
-                index, self.len
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
             );
         }
         self.arr[self.len - index - 1].as_ref().unwrap_or_else(|| {
@@ -74,8 +326,8 @@ impl<T, const N: usize> IndexMut<usize> for Stack<T, N> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         if index >= self.len {
             panic!(
-                "Attempt to access invalid index {} on Stack with length {}",
-                index, self.len
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
             );
         }
         self.arr[self.len - index - 1].as_mut().unwrap_or_else(|| {
@@ -87,6 +339,15 @@ impl<T, const N: usize> IndexMut<usize> for Stack<T, N> {
     }
 }
 
+impl<'a, T, const N: usize> IntoIterator for &'a Stack<T, N> {
+    type Item = &'a T;
+    type IntoIter = StackIter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 pub struct StackIter<'a, T, const N: usize> {
     base: &'a Stack<T, N>,
     index: usize,
@@ -106,6 +367,21 @@ impl<'a, T, const N: usize> Iterator for StackIter<'a, T, N> {
     }
 }
 
+pub struct StackIterMut<'a, T> {
+    inner: Rev<slice::IterMut<'a, Option<T>>>,
+}
+
+impl<'a, T> Iterator for StackIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|elem| {
+            elem.as_mut()
+                .unwrap_or_else(|| panic!("Unexpected None in backing array of Stack"))
+        })
+    }
+}
+
 #[macro_export]
 macro_rules! stack {
     [$($elem:expr),*] => {{
@@ -118,6 +394,8 @@ macro_rules! stack {
 
 #[cfg(test)]
 mod tests {
+    use core::{cell::Cell, panic::AssertUnwindSafe};
+
     use super::*;
 
     #[test]
@@ -213,7 +491,7 @@ mod tests {
     #[test]
     fn test_pop() {
         let mut stack: Stack<u32, 10> = stack![1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
-        let mut exp_arr = stack.arr.clone();
+        let mut exp_arr = stack.arr;
         let mut exp_len = stack.len();
 
         assert_eq!(stack.arr, exp_arr);
@@ -309,5 +587,372 @@ mod tests {
             assert_eq!(i, *n as usize);
         }
     }
-}
 
+    #[test]
+    fn test_iter_mut() {
+        let mut stack: Stack<u32, 10> = stack![1, 2, 3, 4];
+
+        let mut visited = [0; 4];
+        for (i, n) in stack.iter_mut().enumerate() {
+            visited[i] = *n;
+            *n *= 2;
+        }
+
+        assert_eq!(visited, [4, 3, 2, 1]);
+
+        for (i, n) in stack.iter().enumerate() {
+            assert_eq!(*n, visited[i] * 2);
+        }
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        let stack = Stack::<u8, 5>::try_from("abc").unwrap();
+        assert_eq!(stack.len, 3);
+        assert_eq!(stack.arr, [Some(b'a'), Some(b'b'), Some(b'c'), None, None]);
+        assert_eq!(stack[0], b'c');
+
+        assert_eq!(
+            Stack::<u8, 5>::try_from("abcdef").err(),
+            Some(CapacityError {
+                capacity: 5,
+                required: 6
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to increment len 2 past capacity 2")]
+    fn test_inc_len_past_capacity() {
+        let mut stack = Stack::<u32, 2>::new();
+        stack.push(1);
+        stack.push(2);
+
+        stack.inc_len();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Attempt to decrement len below 0")]
+    fn test_dec_len_below_zero() {
+        let mut stack = Stack::<u32, 2>::new();
+        stack.dec_len();
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let mut stack = Stack::<u32, 5>::from_fn(|i| i as u32 * i as u32);
+        assert_eq!(stack.len, 5);
+        assert_eq!(stack.arr, [0, 1, 4, 9, 16].map(Some));
+        assert_eq!(stack.pop(), Some(16));
+    }
+
+    #[test]
+    fn test_to_capacity() {
+        let stack: Stack<u32, 4> = stack![1, 2, 3];
+
+        let stack: Stack<u32, 8> = stack
+            .to_capacity()
+            .unwrap_or_else(|_| panic!("Expected stack to fit"));
+        assert_eq!(stack.len, 3);
+        assert_eq!(
+            stack.arr,
+            [Some(1), Some(2), Some(3), None, None, None, None, None]
+        );
+
+        let stack = stack.to_capacity::<2>().err().unwrap();
+        assert_eq!(stack.len, 3);
+        assert_eq!(
+            stack.arr,
+            [Some(1), Some(2), Some(3), None, None, None, None, None]
+        );
+    }
+
+    #[test]
+    fn test_contains_within() {
+        let stack: Stack<u32, 10> = stack![1, 2, 3, 4, 5];
+
+        assert!(stack.contains_within(2, &5));
+        assert!(stack.contains_within(2, &4));
+        assert!(!stack.contains_within(2, &3));
+        assert!(!stack.contains_within(2, &1));
+        assert!(!stack.contains_within(0, &5));
+        assert!(stack.contains_within(20, &1));
+    }
+
+    #[test]
+    fn test_push_all() {
+        let mut stack = Stack::<u32, 4>::new();
+
+        let mut rest = stack.push_all([1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(rest.next(), Some(5));
+        assert_eq!(rest.next(), Some(6));
+        assert_eq!(rest.next(), None);
+
+        assert_eq!(stack.len, 4);
+        assert_eq!(stack.arr, [1, 2, 3, 4].map(Some));
+
+        let mut stack = Stack::<u32, 4>::new();
+        assert!(stack.push_all([1, 2]).is_none());
+        assert_eq!(stack.arr, [Some(1), Some(2), None, None]);
+
+        // Exactly filling the stack still counts as everything fitting
+        assert!(stack.push_all([3, 4]).is_none());
+        assert_eq!(stack.arr, [1, 2, 3, 4].map(Some));
+        assert!(stack.push_all([]).is_none());
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let stack: Stack<u32, 10> = stack![1, 2, 3, 4];
+        assert_eq!(stack.sum(), 10);
+        assert_eq!(stack.product(), 24);
+
+        let empty = Stack::<u32, 10>::new();
+        assert_eq!(empty.sum(), 0);
+        assert_eq!(empty.product(), 1);
+    }
+
+    #[test]
+    fn test_pop_if() {
+        let mut stack: Stack<u32, 10> = stack![1, 2, 3];
+
+        assert_eq!(stack.pop_if(|n| *n == 3), Some(3));
+        assert_eq!(stack.len, 2);
+
+        assert_eq!(stack.pop_if(|n| *n == 1), None);
+        assert_eq!(stack.len, 2);
+        assert_eq!(stack[0], 2);
+
+        let mut empty = Stack::<u32, 10>::new();
+        assert_eq!(empty.pop_if(|_| true), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn test_index_oob_panic() {
+        let stack: Stack<u32, 10> = stack![1, 2, 3];
+        let _ = stack[3];
+    }
+
+    #[test]
+    fn test_into_iter_ref() {
+        let stack: Stack<u32, 10> = stack![1, 2, 3];
+
+        let mut expected = 3;
+        for elem in &stack {
+            assert_eq!(*elem, expected);
+            expected -= 1;
+        }
+
+        assert_eq!(expected, 0);
+    }
+
+    #[test]
+    fn test_iter_bottom_up() {
+        let stack: Stack<u32, 10> = stack![1, 2, 3];
+
+        assert!(stack.iter_bottom_up().eq([1, 2, 3].iter()));
+        assert!(stack.iter().eq([3, 2, 1].iter()));
+
+        let mut expected = 3;
+        stack.for_each_top_down(|elem| {
+            assert_eq!(*elem, expected);
+            expected -= 1;
+        });
+        assert_eq!(expected, 0);
+    }
+
+    #[test]
+    fn test_extend_copied() {
+        let other: Stack<u32, 4> = stack![1, 2, 3];
+        let mut stack: Stack<u32, 8> = stack![0];
+
+        // Iterating another stack goes from the top down, so the order is reversed
+        stack.extend(other.iter());
+
+        assert!(stack.iter().copied().eq([1, 2, 3, 0]));
+    }
+
+    #[test]
+    fn test_drain_with() {
+        let mut coll: Stack<u32, 5> = stack![1, 2, 3];
+        let mut drained = [0; 3];
+        let mut count = 0;
+
+        coll.drain_with(|elem| {
+            drained[count] = elem;
+            count += 1;
+        });
+
+        assert_eq!(count, 3);
+        assert_eq!(drained, [3, 2, 1]);
+        assert_eq!(coll.len, 0);
+        assert_eq!(coll.arr, [None; 5]);
+    }
+
+    #[test]
+    fn test_try_for_each() {
+        let coll: Stack<u32, 10> = stack![1, 2, 3, 4];
+        let mut visited = [0; 4];
+        let mut count = 0;
+
+        let result = coll.try_for_each(|elem| {
+            if *elem == 2 {
+                return Err(*elem * 10);
+            }
+            visited[count] = *elem;
+            count += 1;
+            Ok(())
+        });
+
+        assert_eq!(result, Err(20));
+        assert_eq!(visited[..count], [4, 3]);
+        assert_eq!(coll.try_for_each(|_| Ok::<(), ()>(())), Ok(()));
+    }
+
+    #[test]
+    fn test_indexed() {
+        let coll: Stack<u32, 10> = stack![10, 20, 30];
+
+        let indexed = coll.indexed();
+        assert_eq!(indexed.len(), 3);
+        assert!(indexed.eq([(0, &30), (1, &20), (2, &10)]));
+        assert_eq!(coll.indexed().nth(1), Some((1, &20)));
+    }
+
+    // An element that counts its drops, and panics when dropped if `panics` is set
+    struct DropBomb<'a> {
+        drops: &'a Cell<usize>,
+        panics: bool,
+    }
+    impl Drop for DropBomb<'_> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panics {
+                panic!("DropBomb exploded");
+            }
+        }
+    }
+
+    #[test]
+    fn test_clear_panicking_drop() {
+        extern crate std;
+
+        let drops = Cell::new(0);
+        let mut stack: Stack<DropBomb, 5> = Stack::new();
+        stack.push(DropBomb {
+            drops: &drops,
+            panics: false,
+        });
+        stack.push(DropBomb {
+            drops: &drops,
+            panics: true,
+        });
+        stack.push(DropBomb {
+            drops: &drops,
+            panics: false,
+        });
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| stack.clear()));
+
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 3);
+        assert_eq!(stack.len, 0);
+        assert!(stack.arr.iter().all(Option::is_none));
+
+        stack.push(DropBomb {
+            drops: &drops,
+            panics: false,
+        });
+        assert_eq!(stack.len, 1);
+        drop(stack);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn test_assert_can_push() {
+        let mut stack: Stack<u32, 4> = Stack::new();
+        stack.assert_can_push(4);
+        stack.assert_can_push(0);
+
+        stack.push(1);
+        stack.assert_can_push(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot push 5 elements: only 4 slots free")]
+    fn test_assert_can_push_panic() {
+        let stack: Stack<u32, 4> = Stack::new();
+        stack.assert_can_push(5);
+    }
+
+    #[test]
+    fn test_keep_prefix() {
+        let mut stack: Stack<u32, 8> = stack![1, 2, 3, 4, 5];
+        stack.keep_prefix(3);
+        assert_eq!(
+            stack.arr,
+            [Some(1), Some(2), Some(3), None, None, None, None, None]
+        );
+        assert_eq!(stack.len, 3);
+
+        stack.keep_prefix(4);
+        assert_eq!(stack.len, 3);
+    }
+
+    #[test]
+    fn test_keep_suffix() {
+        let mut stack: Stack<u32, 8> = stack![1, 2, 3, 4, 5];
+        stack.keep_suffix(2);
+        assert_eq!(
+            stack.arr,
+            [Some(4), Some(5), None, None, None, None, None, None]
+        );
+        assert_eq!(stack.len, 2);
+        assert_eq!(stack.pop(), Some(5));
+
+        stack.keep_suffix(0);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        let mut stack: Stack<u32, 8> = stack![1, 20, 3, 40];
+        stack.map_in_place(|elem| *elem = (*elem).min(10));
+        assert_eq!(stack.arr[..4], [Some(1), Some(10), Some(3), Some(10)]);
+
+        let mut order = [0; 4];
+        let mut i = 0;
+        stack.map_in_place(|elem| {
+            order[i] = *elem;
+            i += 1;
+        });
+        assert_eq!(order, [10, 3, 10, 1]);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut stack = Stack::<u32, 0>::new();
+
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.pop_if(|_| true), None);
+        assert_eq!(stack.iter().count(), 0);
+        stack.assert_can_push(0);
+        stack.keep_suffix(0);
+        stack.clear();
+
+        // Pushing returns the iterator untouched, since there is never room
+        let mut rest = stack.push_all([1, 2]).unwrap();
+        assert_eq!(rest.next(), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Attempt to add value to full stack")]
+    fn test_zero_capacity_push_panic() {
+        let mut stack = Stack::<u32, 0>::new();
+        stack.push(1);
+    }
+}